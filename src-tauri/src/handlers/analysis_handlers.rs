@@ -0,0 +1,35 @@
+use tauri::AppHandle;
+
+use crate::utils::analysis_utils::{
+    self, CropDetectionResult, SceneDetectionResult, SilenceDetectionResult,
+};
+use crate::utils::app_dir_utils::get_app_cache_dir;
+
+#[tauri::command]
+pub async fn detect_crop(video_path: String) -> Result<CropDetectionResult, String> {
+    analysis_utils::detect_crop(&video_path)
+}
+
+#[tauri::command]
+pub async fn detect_scenes(
+    app: AppHandle,
+    video_path: String,
+    threshold: Option<f64>,
+    generate_thumbnails: bool,
+) -> Result<SceneDetectionResult, String> {
+    analysis_utils::detect_scenes(
+        &video_path,
+        threshold,
+        generate_thumbnails,
+        &get_app_cache_dir(&app)?,
+    )
+}
+
+#[tauri::command]
+pub async fn detect_silence(
+    video_path: String,
+    noise_threshold_db: Option<f64>,
+    min_silence_seconds: Option<f64>,
+) -> Result<SilenceDetectionResult, String> {
+    analysis_utils::detect_silence(&video_path, noise_threshold_db, min_silence_seconds)
+}
@@ -1,8 +1,16 @@
 use std::{os::windows::process::CommandExt, process::Command, thread};
 
+use crate::utils::animation_utils::{self, AnimationExportOptions};
+use crate::utils::audio_utils::{self, AudioStreamInfo};
+use crate::utils::crf_search_utils::{self, CrfSearchOptions, CrfSearchResult};
 use crate::utils::ffmpeg_utils::{
     self, DependenciesSetUpInfo, VideoEditOptions, VideoEditProgress, VideoInfo,
 };
+use crate::utils::quality_utils::{self, QualityComparisonResult};
+use crate::utils::sample_utils::{self, SampleEncodeOptions, SampleEncodePreview};
+use crate::utils::sanitize_utils::{self, SanitizeReport};
+use crate::utils::subtitle_utils::{self, SubtitleFormat, SubtitleMode, SubtitleStreamInfo};
+use crate::utils::validation_utils::{self, ValidationReport};
 
 pub const CREATE_NO_WINDOW: u32 = 0x08000000;
 
@@ -33,7 +41,9 @@ fn is_command_available(command: &str) -> bool {
 #[tauri::command]
 pub fn check_ffmpeg_and_ffprobe() -> bool {
     println!("Checking for ffmpeg and ffprobe");
-    if is_command_available("ffmpeg") && is_command_available("ffprobe") {
+    if is_command_available(&ffmpeg_utils::get_ffmpeg_command())
+        && is_command_available(&ffmpeg_utils::get_ffprobe_command())
+    {
         return true;
     }
     ffmpeg_utils::add_ffmpeg_to_app_env_if_it_exists()
@@ -45,25 +55,137 @@ pub fn get_video_info(video_path: &str) -> Result<VideoInfo, String> {
 }
 
 #[tauri::command]
-pub fn submit_video_for_editing(options: VideoEditOptions) {
+pub fn get_audio_streams(video_path: &str) -> Result<Vec<AudioStreamInfo>, String> {
+    audio_utils::get_audio_streams(video_path)
+}
+
+#[tauri::command]
+pub fn get_subtitle_streams(video_path: &str) -> Result<Vec<SubtitleStreamInfo>, String> {
+    subtitle_utils::get_subtitle_streams(video_path)
+}
+
+#[tauri::command]
+pub fn extract_subtitles(
+    options: VideoEditOptions,
+    subtitle_index: u32,
+    format: SubtitleFormat,
+) -> Result<String, String> {
+    subtitle_utils::extract_subtitles(&options, subtitle_index, format)
+}
+
+#[tauri::command]
+pub fn validate_edit_options(options: VideoEditOptions) -> ValidationReport {
+    validation_utils::validate_edit_options(&options)
+}
+
+#[tauri::command]
+pub fn submit_video_for_editing(options: VideoEditOptions) -> Result<(), String> {
+    validation_utils::validate_edit_options(&options).into_result()?;
+    let job = ffmpeg_utils::try_start_video_job()?;
     ffmpeg_utils::clear_video_progress();
 
     thread::spawn(move || {
-        ffmpeg_utils::process_video(options.clone());
+        let _job = job;
+        let _ = ffmpeg_utils::process_video(options.clone());
     });
+
+    Ok(())
 }
 
 #[tauri::command]
-pub fn submit_audio_extraction(options: VideoEditOptions) {
+pub fn submit_audio_extraction(options: VideoEditOptions) -> Result<(), String> {
+    let mut audio_options = options.clone();
+    audio_options.crop_enabled = false;
+    audio_options.resize_enabled = false;
+    audio_options.compression_enabled = false;
+    audio_options.external_audio_enabled = false;
+    audio_options.subtitle_options.mode = SubtitleMode::None;
+    validation_utils::validate_edit_options(&audio_options).into_result()?;
+
     println!(
         "Extracting audio from video: {:?}",
         options.input_video_path.as_str()
     );
+    let job = ffmpeg_utils::try_start_video_job()?;
     ffmpeg_utils::clear_video_progress();
 
     thread::spawn(move || {
+        let _job = job;
         ffmpeg_utils::extract_audio(options.clone());
     });
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn submit_animation_export(
+    options: VideoEditOptions,
+    animation_options: AnimationExportOptions,
+) -> Result<(), String> {
+    validation_utils::validate_animation_export(&options, &animation_options).into_result()?;
+    let job = ffmpeg_utils::try_start_video_job()?;
+    ffmpeg_utils::clear_video_progress();
+
+    thread::spawn(move || {
+        let _job = job;
+        let _ = animation_utils::export_animation(options, animation_options);
+    });
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn sanitize_video(
+    input_video_path: String,
+    output_video_path: String,
+) -> Result<SanitizeReport, String> {
+    validation_utils::validate_sanitize(&input_video_path, &output_video_path).into_result()?;
+    let _job = ffmpeg_utils::try_start_video_job()?;
+    ffmpeg_utils::clear_video_progress();
+
+    sanitize_utils::sanitize_video(&input_video_path, &output_video_path)
+}
+
+#[tauri::command]
+pub async fn compare_video_quality(
+    options: VideoEditOptions,
+    output_video_path: String,
+    segment_seconds: Option<f64>,
+) -> Result<QualityComparisonResult, String> {
+    validation_utils::validate_edit_options(&options).into_result()?;
+    let _job = ffmpeg_utils::try_start_video_job()?;
+    ffmpeg_utils::clear_video_progress();
+
+    quality_utils::run_quality_comparison(&options, &output_video_path, segment_seconds)
+}
+
+#[tauri::command]
+pub async fn preview_sample_encode(
+    options: VideoEditOptions,
+    sample_options: SampleEncodeOptions,
+) -> Result<SampleEncodePreview, String> {
+    validation_utils::validate_sample_encode(&options, &sample_options).into_result()?;
+    let _job = ffmpeg_utils::try_start_video_job()?;
+    ffmpeg_utils::clear_video_progress();
+
+    sample_utils::preview_sample_encode(&options, &sample_options)
+}
+
+#[tauri::command]
+pub fn is_libvmaf_available() -> bool {
+    quality_utils::is_libvmaf_available()
+}
+
+#[tauri::command]
+pub async fn search_crf(
+    options: VideoEditOptions,
+    search_options: CrfSearchOptions,
+) -> Result<CrfSearchResult, String> {
+    validation_utils::validate_crf_search(&options, &search_options).into_result()?;
+    let _job = ffmpeg_utils::try_start_video_job()?;
+    ffmpeg_utils::clear_video_progress();
+
+    crf_search_utils::search_crf(&options, &search_options)
 }
 
 #[tauri::command]
@@ -0,0 +1,26 @@
+use tauri::AppHandle;
+
+use crate::utils::app_dir_utils::get_app_cache_dir;
+use crate::utils::frame_utils::{self, FrameExtractionOptions, SpriteSheet};
+
+#[tauri::command]
+pub async fn extract_frame(options: FrameExtractionOptions) -> Result<String, String> {
+    frame_utils::extract_frame(&options)
+}
+
+#[tauri::command]
+pub async fn generate_sprite_sheet(
+    app: AppHandle,
+    video_path: String,
+    columns: Option<u32>,
+    rows: Option<u32>,
+    thumbnail_width: Option<u32>,
+) -> Result<SpriteSheet, String> {
+    frame_utils::generate_sprite_sheet(
+        &video_path,
+        columns,
+        rows,
+        thumbnail_width,
+        &get_app_cache_dir(&app)?,
+    )
+}
@@ -1,2 +1,7 @@
+pub mod analysis_handlers;
 pub mod ffmpeg_handlers;
+pub mod frame_handlers;
+pub mod preset_handlers;
+pub mod settings_handlers;
 pub mod video_handlers;
+pub mod watch_handlers;
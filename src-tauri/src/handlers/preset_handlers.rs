@@ -0,0 +1,62 @@
+use rfd::FileDialog;
+use tauri::AppHandle;
+
+use crate::utils::app_dir_utils::get_app_config_dir;
+use crate::utils::ffmpeg_utils::VideoEditOptions;
+use crate::utils::preset_utils::{self, EditPreset};
+
+#[tauri::command]
+pub fn list_presets(app: AppHandle) -> Result<Vec<EditPreset>, String> {
+    preset_utils::list_presets(&get_app_config_dir(&app)?)
+}
+
+#[tauri::command]
+pub fn save_preset(app: AppHandle, preset: EditPreset) -> Result<(), String> {
+    preset_utils::save_preset(&get_app_config_dir(&app)?, preset)
+}
+
+#[tauri::command]
+pub fn delete_preset(app: AppHandle, name: String) -> Result<(), String> {
+    preset_utils::delete_preset(&get_app_config_dir(&app)?, &name)
+}
+
+#[tauri::command]
+pub fn apply_preset(
+    app: AppHandle,
+    name: String,
+    input_video_path: String,
+    output_video_path: String,
+) -> Result<VideoEditOptions, String> {
+    let preset = preset_utils::get_preset(&get_app_config_dir(&app)?, &name)?;
+    preset_utils::apply_preset(&preset, &input_video_path, &output_video_path)
+}
+
+#[tauri::command]
+pub fn import_presets(app: AppHandle) -> Result<Vec<String>, String> {
+    let picked_file = FileDialog::new()
+        .set_title("Import Presets")
+        .add_filter("Preset files", &["json"])
+        .pick_file();
+
+    match picked_file {
+        Some(path) => preset_utils::import_presets(&get_app_config_dir(&app)?, &path),
+        None => Ok(Vec::new()),
+    }
+}
+
+#[tauri::command]
+pub fn export_presets(app: AppHandle, names: Vec<String>) -> Result<bool, String> {
+    let picked_file = FileDialog::new()
+        .set_title("Export Presets")
+        .add_filter("Preset files", &["json"])
+        .set_file_name("VideoCrop_presets.json")
+        .save_file();
+
+    match picked_file {
+        Some(path) => {
+            preset_utils::export_presets(&get_app_config_dir(&app)?, &names, &path)?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
@@ -0,0 +1,11 @@
+use crate::utils::settings_utils::{self, Settings};
+
+#[tauri::command]
+pub fn get_settings() -> Settings {
+    settings_utils::get_settings()
+}
+
+#[tauri::command]
+pub fn update_settings(settings: Settings) -> Result<Settings, String> {
+    settings_utils::update_settings(settings)
+}
@@ -1,17 +1,28 @@
 use rfd::FileDialog;
 
+use crate::utils::settings_utils;
+
 const ALLOWED_VIDEO_EXTENSIONS: [&str; 5] = ["mp4", "avi", "mov", "mkv", "webm"];
+const ALLOWED_SUBTITLE_EXTENSIONS: [&str; 3] = ["srt", "ass", "ssa"];
+const ALLOWED_AUDIO_EXTENSIONS: [&str; 9] = [
+    "wav", "flac", "mp3", "m4a", "aac", "opus", "ogg", "mka", "wma",
+];
 
 #[tauri::command]
 pub fn open_video() -> String {
+    let settings = settings_utils::get_settings();
+
     loop {
-        let picked_file = FileDialog::new()
-            .add_filter("Video files", &ALLOWED_VIDEO_EXTENSIONS)
-            .pick_file();
+        let mut file_dialog =
+            FileDialog::new().add_filter("Video files", &ALLOWED_VIDEO_EXTENSIONS);
+        if let Some(last_input_directory) = &settings.last_input_directory {
+            file_dialog = file_dialog.set_directory(last_input_directory);
+        }
 
-        match picked_file {
+        match file_dialog.pick_file() {
             Some(picked_f) => {
                 if check_if_file_is_video(picked_f.to_str().unwrap()) {
+                    settings_utils::set_last_input_directory(&picked_f);
                     return picked_f.to_string_lossy().to_string();
                 }
             }
@@ -22,20 +33,64 @@ pub fn open_video() -> String {
     }
 }
 
+#[tauri::command]
+pub fn pick_audio_file() -> String {
+    let settings = settings_utils::get_settings();
+
+    let mut file_dialog = FileDialog::new()
+        .set_title("Select Audio File")
+        .add_filter("Audio files", &ALLOWED_AUDIO_EXTENSIONS)
+        .add_filter("Video files", &ALLOWED_VIDEO_EXTENSIONS);
+    if let Some(last_input_directory) = &settings.last_input_directory {
+        file_dialog = file_dialog.set_directory(last_input_directory);
+    }
+
+    match file_dialog.pick_file() {
+        Some(picked_f) => picked_f.to_string_lossy().to_string(),
+        None => String::from("No file selected"),
+    }
+}
+
+#[tauri::command]
+pub fn pick_subtitle_file() -> String {
+    let settings = settings_utils::get_settings();
+
+    let mut file_dialog = FileDialog::new()
+        .set_title("Select Subtitle File")
+        .add_filter("Subtitle files", &ALLOWED_SUBTITLE_EXTENSIONS);
+    if let Some(last_input_directory) = &settings.last_input_directory {
+        file_dialog = file_dialog.set_directory(last_input_directory);
+    }
+
+    match file_dialog.pick_file() {
+        Some(picked_f) => picked_f.to_string_lossy().to_string(),
+        None => String::from("No file selected"),
+    }
+}
+
 #[tauri::command]
 pub fn pick_output_path() -> String {
     println!("Picking output path");
-    let file = FileDialog::new()
-        .set_title("Select Output Path")
-        .pick_folder();
+    let settings = settings_utils::get_settings();
 
-    match file {
-        Some(path) => path.to_string_lossy().to_string(),
+    let mut file_dialog = FileDialog::new().set_title("Select Output Path");
+    if let Some(output_directory) = settings
+        .last_output_directory
+        .or(settings.default_output_directory)
+    {
+        file_dialog = file_dialog.set_directory(output_directory);
+    }
+
+    match file_dialog.pick_folder() {
+        Some(path) => {
+            settings_utils::set_last_output_directory(&path);
+            path.to_string_lossy().to_string()
+        }
         None => String::from("No path selected"),
     }
 }
 
-fn check_if_file_is_video(file_path: &str) -> bool {
+pub fn check_if_file_is_video(file_path: &str) -> bool {
     ALLOWED_VIDEO_EXTENSIONS
         .iter()
         .any(|&ext| file_path.to_lowercase().ends_with(ext))
@@ -0,0 +1,21 @@
+use tauri::AppHandle;
+
+use crate::utils::app_dir_utils::get_app_config_dir;
+use crate::utils::preset_utils;
+use crate::utils::watch_utils::{self, WatchFolderOptions, WatchFolderStatus};
+
+#[tauri::command]
+pub fn start_watch_folder(app: AppHandle, options: WatchFolderOptions) -> Result<(), String> {
+    let preset = preset_utils::get_preset(&get_app_config_dir(&app)?, &options.preset_name)?;
+    watch_utils::start_watch_folder(options, preset)
+}
+
+#[tauri::command]
+pub fn stop_watch_folder() {
+    watch_utils::stop_watch_folder();
+}
+
+#[tauri::command]
+pub fn get_watch_folder_status() -> WatchFolderStatus {
+    watch_utils::get_watch_folder_status()
+}
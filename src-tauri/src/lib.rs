@@ -4,16 +4,49 @@ mod utils;
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
+        .setup(|app| {
+            let config_dir = utils::app_dir_utils::get_app_config_dir(app.handle())?;
+            utils::settings_utils::load_settings(&config_dir);
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             handlers::video_handlers::open_video,
             handlers::video_handlers::pick_output_path,
+            handlers::video_handlers::pick_audio_file,
+            handlers::video_handlers::pick_subtitle_file,
             handlers::ffmpeg_handlers::check_ffmpeg_and_ffprobe,
             handlers::ffmpeg_handlers::get_video_info,
+            handlers::ffmpeg_handlers::get_audio_streams,
+            handlers::ffmpeg_handlers::get_subtitle_streams,
+            handlers::ffmpeg_handlers::extract_subtitles,
+            handlers::ffmpeg_handlers::validate_edit_options,
             handlers::ffmpeg_handlers::submit_video_for_editing,
             handlers::ffmpeg_handlers::get_video_progress_info,
             handlers::ffmpeg_handlers::download_ffmpeg_windows,
             handlers::ffmpeg_handlers::get_depencencies_download_info,
             handlers::ffmpeg_handlers::submit_audio_extraction,
+            handlers::ffmpeg_handlers::submit_animation_export,
+            handlers::ffmpeg_handlers::sanitize_video,
+            handlers::ffmpeg_handlers::compare_video_quality,
+            handlers::ffmpeg_handlers::preview_sample_encode,
+            handlers::ffmpeg_handlers::search_crf,
+            handlers::ffmpeg_handlers::is_libvmaf_available,
+            handlers::analysis_handlers::detect_crop,
+            handlers::analysis_handlers::detect_scenes,
+            handlers::analysis_handlers::detect_silence,
+            handlers::frame_handlers::extract_frame,
+            handlers::frame_handlers::generate_sprite_sheet,
+            handlers::preset_handlers::list_presets,
+            handlers::preset_handlers::save_preset,
+            handlers::preset_handlers::delete_preset,
+            handlers::preset_handlers::apply_preset,
+            handlers::preset_handlers::import_presets,
+            handlers::preset_handlers::export_presets,
+            handlers::settings_handlers::get_settings,
+            handlers::settings_handlers::update_settings,
+            handlers::watch_handlers::start_watch_folder,
+            handlers::watch_handlers::stop_watch_folder,
+            handlers::watch_handlers::get_watch_folder_status,
         ])
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_os::init())
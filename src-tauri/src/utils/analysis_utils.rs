@@ -0,0 +1,485 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::utils::cache_utils;
+use crate::utils::ffmpeg_utils::{self, VideoCropPoints, VideoEditOptions};
+use crate::utils::timestamp_utils::Timestamp;
+
+const CROP_DETECT_SAMPLE_COUNT: usize = 12;
+const CROP_DETECT_SAMPLE_SECONDS: f64 = 2.0;
+const CROP_DETECT_EDGE_TOLERANCE: i32 = 4;
+const DEFAULT_SCENE_THRESHOLD: f64 = 0.3;
+const SCENE_THUMBNAIL_WIDTH: u32 = 160;
+const DEFAULT_SILENCE_NOISE_DB: f64 = -30.0;
+const DEFAULT_SILENCE_MIN_SECONDS: f64 = 0.5;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CropDetectionResult {
+    pub crop_points: VideoCropPoints,
+    pub confidence: f64,
+    pub samples_used: usize,
+    pub samples_total: usize,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SceneChange {
+    pub time: Timestamp,
+    pub seconds: f64,
+    pub score: f64,
+    pub thumbnail_path: Option<String>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SceneDetectionResult {
+    pub threshold: f64,
+    pub scenes: Vec<SceneChange>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SilenceInterval {
+    pub start: Timestamp,
+    pub end: Timestamp,
+    pub start_seconds: f64,
+    pub end_seconds: f64,
+    pub duration: f64,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SilenceDetectionResult {
+    pub noise_threshold_db: f64,
+    pub min_silence_seconds: f64,
+    pub total_silence_seconds: f64,
+    pub silences: Vec<SilenceInterval>,
+}
+
+#[derive(Clone, Copy)]
+struct CropEdges {
+    left: i32,
+    top: i32,
+    right: i32,
+    bottom: i32,
+}
+
+fn get_sample_times(duration: f64, sample_count: usize, sample_length: f64) -> Vec<f64> {
+    if duration <= sample_length || sample_count == 0 {
+        return vec![0.0];
+    }
+
+    // Skip the first and last 10% where intros, fades and credits usually are.
+    let range_start = duration * 0.1;
+    let range_end = (duration * 0.9 - sample_length).max(range_start);
+    if sample_count == 1 {
+        return vec![range_start];
+    }
+
+    let step = (range_end - range_start) / (sample_count - 1) as f64;
+    (0..sample_count)
+        .map(|i| range_start + step * i as f64)
+        .collect()
+}
+
+fn parse_last_crop(log: &str) -> Option<(i32, i32, i32, i32)> {
+    let crop_start = log.rfind("crop=")? + "crop=".len();
+
+    let values: Vec<i32> = log[crop_start..]
+        .split_whitespace()
+        .next()?
+        .split(':')
+        .map(|value| value.parse::<i32>())
+        .collect::<Result<Vec<i32>, _>>()
+        .ok()?;
+
+    match values.as_slice() {
+        [width, height, x, y] => Some((*width, *height, *x, *y)),
+        _ => None,
+    }
+}
+
+fn median(mut values: Vec<i32>) -> i32 {
+    values.sort_unstable();
+    values[values.len() / 2]
+}
+
+pub fn detect_crop(video_path: &str) -> Result<CropDetectionResult, String> {
+    let video_info = ffmpeg_utils::get_video_info(video_path)?;
+    let duration = video_info.duration.to_seconds(None)?;
+    let frame_width = video_info.width as i32;
+    let frame_height = video_info.height as i32;
+
+    let sample_times = get_sample_times(
+        duration,
+        CROP_DETECT_SAMPLE_COUNT,
+        CROP_DETECT_SAMPLE_SECONDS,
+    );
+    let samples_total = sample_times.len();
+
+    let mut samples: Vec<CropEdges> = Vec::new();
+    for sample_time in sample_times {
+        let args = vec![
+            "-hide_banner".to_string(),
+            "-ss".to_string(),
+            format!("{:.3}", sample_time),
+            "-i".to_string(),
+            video_path.to_string(),
+            "-t".to_string(),
+            format!("{:.3}", CROP_DETECT_SAMPLE_SECONDS),
+            "-an".to_string(),
+            "-sn".to_string(),
+            "-dn".to_string(),
+            "-vf".to_string(),
+            "cropdetect=limit=24:round=2:reset=0".to_string(),
+            "-f".to_string(),
+            "null".to_string(),
+            "-".to_string(),
+        ];
+
+        let log = match ffmpeg_utils::run_ffmpeg_for_log(&args) {
+            Ok(log) => log,
+            Err(err) => {
+                eprintln!("Crop detection sample at {}s failed: {}", sample_time, err);
+                continue;
+            }
+        };
+
+        let (width, height, x, y) = match parse_last_crop(&log) {
+            Some(crop) => crop,
+            None => continue,
+        };
+
+        // Mostly black frames (fades, scene transitions) produce tiny or inverted crops.
+        if width < frame_width / 4 || height < frame_height / 4 || x < 0 || y < 0 {
+            continue;
+        }
+
+        samples.push(CropEdges {
+            left: x,
+            top: y,
+            right: (frame_width - x - width).max(0),
+            bottom: (frame_height - y - height).max(0),
+        });
+    }
+
+    if samples.is_empty() {
+        return Ok(CropDetectionResult {
+            crop_points: VideoCropPoints {
+                starting_x_offset: 0,
+                starting_y_offset: 0,
+                width: frame_width,
+                height: frame_height,
+            },
+            confidence: 0.0,
+            samples_used: 0,
+            samples_total,
+        });
+    }
+
+    let edges = CropEdges {
+        left: median(samples.iter().map(|s| s.left).collect()),
+        top: median(samples.iter().map(|s| s.top).collect()),
+        right: median(samples.iter().map(|s| s.right).collect()),
+        bottom: median(samples.iter().map(|s| s.bottom).collect()),
+    };
+
+    let agreeing_samples = samples
+        .iter()
+        .filter(|s| {
+            (s.left - edges.left).abs() <= CROP_DETECT_EDGE_TOLERANCE
+                && (s.top - edges.top).abs() <= CROP_DETECT_EDGE_TOLERANCE
+                && (s.right - edges.right).abs() <= CROP_DETECT_EDGE_TOLERANCE
+                && (s.bottom - edges.bottom).abs() <= CROP_DETECT_EDGE_TOLERANCE
+        })
+        .count();
+
+    let width = frame_width - edges.left - edges.right;
+    let height = frame_height - edges.top - edges.bottom;
+
+    Ok(CropDetectionResult {
+        crop_points: VideoCropPoints {
+            starting_x_offset: edges.left,
+            starting_y_offset: edges.top,
+            width: width - width % 2,
+            height: height - height % 2,
+        },
+        confidence: agreeing_samples as f64 / samples_total as f64,
+        samples_used: samples.len(),
+        samples_total,
+    })
+}
+
+pub fn extract_thumbnail(
+    video_path: &str,
+    seconds: f64,
+    width: u32,
+    output_path: &Path,
+) -> Result<(), String> {
+    let args = vec![
+        "-hide_banner".to_string(),
+        "-y".to_string(),
+        "-ss".to_string(),
+        format!("{:.3}", seconds),
+        "-i".to_string(),
+        video_path.to_string(),
+        "-frames:v".to_string(),
+        "1".to_string(),
+        "-vf".to_string(),
+        format!("scale={}:-2", width),
+        "-q:v".to_string(),
+        "4".to_string(),
+        output_path.to_string_lossy().to_string(),
+    ];
+
+    ffmpeg_utils::run_ffmpeg_for_log(&args).map(|_| ())
+}
+
+fn parse_scene_changes(log: &str) -> Vec<(f64, f64)> {
+    let mut scene_changes = Vec::new();
+    let mut current_time: Option<f64> = None;
+
+    for line in log.lines() {
+        if let Some(index) = line.find("pts_time:") {
+            current_time = line[index + "pts_time:".len()..]
+                .split_whitespace()
+                .next()
+                .and_then(|value| value.parse::<f64>().ok());
+            continue;
+        }
+
+        if let Some(index) = line.find("lavfi.scene_score=") {
+            let score = line[index + "lavfi.scene_score=".len()..]
+                .trim()
+                .parse::<f64>()
+                .ok();
+
+            if let (Some(time), Some(score)) = (current_time.take(), score) {
+                scene_changes.push((time, score));
+            }
+        }
+    }
+
+    scene_changes
+}
+
+pub fn detect_scenes(
+    video_path: &str,
+    threshold: Option<f64>,
+    generate_thumbnails: bool,
+    cache_dir: &Path,
+) -> Result<SceneDetectionResult, String> {
+    let threshold = threshold.unwrap_or(DEFAULT_SCENE_THRESHOLD).clamp(0.0, 1.0);
+
+    let cache_key = cache_utils::get_file_cache_key(
+        video_path,
+        &format!("scenes:{:.3}:{}", threshold, generate_thumbnails),
+    )?;
+    let cache_file_name = format!("scenes_{}.json", cache_key);
+
+    if let Some(cached) =
+        cache_utils::read_cached::<SceneDetectionResult>(cache_dir, &cache_file_name)
+    {
+        let thumbnails_exist = cached.scenes.iter().all(|scene| {
+            scene
+                .thumbnail_path
+                .as_ref()
+                .is_none_or(|path| Path::new(path).exists())
+        });
+
+        if thumbnails_exist {
+            return Ok(cached);
+        }
+    }
+
+    let args = vec![
+        "-hide_banner".to_string(),
+        "-i".to_string(),
+        video_path.to_string(),
+        "-an".to_string(),
+        "-sn".to_string(),
+        "-dn".to_string(),
+        "-vf".to_string(),
+        format!(
+            "select='gt(scene,{:.3})',metadata=print:key=lavfi.scene_score",
+            threshold
+        ),
+        "-f".to_string(),
+        "null".to_string(),
+        "-".to_string(),
+    ];
+
+    let log = ffmpeg_utils::run_ffmpeg_for_log(&args)?;
+
+    let thumbnail_dir = cache_dir.join(format!("scenes_{}", cache_key));
+    if generate_thumbnails {
+        fs::create_dir_all(&thumbnail_dir)
+            .map_err(|e| format!("Failed to create thumbnail directory: {}", e))?;
+    }
+
+    let scenes = parse_scene_changes(&log)
+        .into_iter()
+        .enumerate()
+        .map(|(index, (seconds, score))| {
+            let thumbnail_path = if generate_thumbnails {
+                let thumbnail_path = thumbnail_dir.join(format!("scene_{:04}.jpg", index));
+                match extract_thumbnail(video_path, seconds, SCENE_THUMBNAIL_WIDTH, &thumbnail_path)
+                {
+                    Ok(()) => Some(thumbnail_path.to_string_lossy().to_string()),
+                    Err(err) => {
+                        eprintln!("Failed to extract scene thumbnail: {}", err);
+                        None
+                    }
+                }
+            } else {
+                None
+            };
+
+            SceneChange {
+                time: Timestamp::from_seconds(seconds),
+                seconds,
+                score,
+                thumbnail_path,
+            }
+        })
+        .collect();
+
+    let result = SceneDetectionResult { threshold, scenes };
+    cache_utils::write_cached(cache_dir, &cache_file_name, &result);
+
+    Ok(result)
+}
+
+fn parse_log_value(line: &str, key: &str) -> Option<f64> {
+    let index = line.find(key)?;
+    line[index + key.len()..]
+        .split_whitespace()
+        .next()?
+        .parse::<f64>()
+        .ok()
+}
+
+fn parse_silences(log: &str, video_length: f64) -> Vec<(f64, f64)> {
+    let mut silences = Vec::new();
+    let mut current_start: Option<f64> = None;
+
+    for line in log.lines() {
+        if let Some(start) = parse_log_value(line, "silence_start:") {
+            current_start = Some(start.max(0.0));
+        } else if let Some(end) = parse_log_value(line, "silence_end:") {
+            if let Some(start) = current_start.take() {
+                silences.push((start, end.min(video_length)));
+            }
+        }
+    }
+
+    // Silence running into the end of the file never gets a silence_end line.
+    if let Some(start) = current_start {
+        if start < video_length {
+            silences.push((start, video_length));
+        }
+    }
+
+    silences
+}
+
+fn find_silences(
+    video_path: &str,
+    noise_threshold_db: f64,
+    min_silence_seconds: f64,
+) -> Result<Vec<(f64, f64)>, String> {
+    let video_length = ffmpeg_utils::get_video_length_in_seconds(video_path)?;
+
+    let args = vec![
+        "-hide_banner".to_string(),
+        "-i".to_string(),
+        video_path.to_string(),
+        "-vn".to_string(),
+        "-sn".to_string(),
+        "-dn".to_string(),
+        "-af".to_string(),
+        format!(
+            "silencedetect=noise={:.1}dB:d={:.3}",
+            noise_threshold_db, min_silence_seconds
+        ),
+        "-f".to_string(),
+        "null".to_string(),
+        "-".to_string(),
+    ];
+
+    let log = ffmpeg_utils::run_ffmpeg_for_log(&args)?;
+    Ok(parse_silences(&log, video_length))
+}
+
+pub fn detect_silence(
+    video_path: &str,
+    noise_threshold_db: Option<f64>,
+    min_silence_seconds: Option<f64>,
+) -> Result<SilenceDetectionResult, String> {
+    let noise_threshold_db = noise_threshold_db.unwrap_or(DEFAULT_SILENCE_NOISE_DB);
+    let min_silence_seconds = min_silence_seconds
+        .unwrap_or(DEFAULT_SILENCE_MIN_SECONDS)
+        .max(0.01);
+
+    let silences: Vec<SilenceInterval> =
+        find_silences(video_path, noise_threshold_db, min_silence_seconds)?
+            .into_iter()
+            .map(|(start, end)| SilenceInterval {
+                start: Timestamp::from_seconds(start),
+                end: Timestamp::from_seconds(end),
+                start_seconds: start,
+                end_seconds: end,
+                duration: end - start,
+            })
+            .collect();
+
+    Ok(SilenceDetectionResult {
+        noise_threshold_db,
+        min_silence_seconds,
+        total_silence_seconds: silences.iter().map(|silence| silence.duration).sum(),
+        silences,
+    })
+}
+
+pub fn get_silence_removal_segments(options: &VideoEditOptions) -> Result<Vec<(f64, f64)>, String> {
+    let removal_options = &options.silence_removal_options;
+    let video_length = ffmpeg_utils::get_video_length_in_seconds(&options.input_video_path)?;
+
+    let (range_start, range_end) = if options.cut_options_enabled {
+        let (start, end) = ffmpeg_utils::get_cut_range_in_seconds(options)?;
+        (start, end.min(video_length))
+    } else {
+        (0.0, video_length)
+    };
+
+    let silences = find_silences(
+        &options.input_video_path,
+        removal_options.noise_threshold_db,
+        removal_options.min_silence_seconds,
+    )?;
+
+    // Keep a bit of the silence on both sides so speech is not clipped.
+    let padding = removal_options.padding_seconds.max(0.0);
+    let mut segments: Vec<(f64, f64)> = Vec::new();
+    let mut segment_start = range_start;
+
+    for (silence_start, silence_end) in silences {
+        let cut_start = (silence_start + padding).max(range_start);
+        let cut_end = (silence_end - padding).min(range_end);
+        if cut_end <= cut_start || cut_end <= segment_start {
+            continue;
+        }
+
+        if cut_start > segment_start {
+            segments.push((segment_start, cut_start));
+        }
+        segment_start = cut_end;
+    }
+
+    if segment_start < range_end {
+        segments.push((segment_start, range_end));
+    }
+
+    if segments.is_empty() {
+        return Err("Silence removal would remove the whole video".to_string());
+    }
+
+    Ok(segments)
+}
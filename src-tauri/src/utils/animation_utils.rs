@@ -0,0 +1,251 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use uuid::Uuid;
+
+use crate::utils::ffmpeg_utils::{self, VideoEditOptions};
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnimationFormat {
+    Gif,
+    Webp,
+    Apng,
+}
+
+impl AnimationFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            AnimationFormat::Gif => "gif",
+            AnimationFormat::Webp => "webp",
+            AnimationFormat::Apng => "apng",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GifDither {
+    None,
+    Bayer,
+    FloydSteinberg,
+    #[default]
+    Sierra,
+}
+
+impl GifDither {
+    fn to_filter_value(self) -> &'static str {
+        match self {
+            GifDither::None => "none",
+            GifDither::Bayer => "bayer:bayer_scale=3",
+            GifDither::FloydSteinberg => "floyd_steinberg",
+            GifDither::Sierra => "sierra2_4a",
+        }
+    }
+}
+
+fn default_webp_quality() -> u32 {
+    75
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AnimationExportOptions {
+    pub format: AnimationFormat,
+    pub fps: f64,
+    #[serde(default)]
+    pub max_width: Option<u32>,
+    #[serde(default)]
+    pub max_height: Option<u32>,
+    #[serde(default)]
+    pub dither: GifDither,
+    #[serde(default)]
+    pub loop_count: u32,
+    #[serde(default = "default_webp_quality")]
+    pub webp_quality: u32,
+}
+
+fn get_max_size_filter(max_width: Option<u32>, max_height: Option<u32>) -> Option<String> {
+    match (max_width, max_height) {
+        (Some(width), Some(height)) => Some(format!(
+            "scale=w='min(iw,{})':h='min(ih,{})':force_original_aspect_ratio=decrease:flags=lanczos",
+            width, height
+        )),
+        (Some(width), None) => Some(format!("scale='min(iw,{})':-1:flags=lanczos", width)),
+        (None, Some(height)) => Some(format!("scale=-1:'min(ih,{})':flags=lanczos", height)),
+        (None, None) => None,
+    }
+}
+
+fn get_animation_filters(
+    options: &VideoEditOptions,
+    animation_options: &AnimationExportOptions,
+) -> String {
+    let mut filters = ffmpeg_utils::get_geometry_filters(options);
+    filters.push(format!("fps={:.3}", animation_options.fps));
+
+    if let Some(max_size_filter) =
+        get_max_size_filter(animation_options.max_width, animation_options.max_height)
+    {
+        filters.push(max_size_filter);
+    }
+
+    filters.join(",")
+}
+
+fn get_input_args(options: &VideoEditOptions) -> Result<(Vec<String>, f64), String> {
+    let output_length = ffmpeg_utils::get_output_length_in_seconds(options)?;
+    let mut args = Vec::new();
+
+    // Seek on the input so both GIF passes only decode the part that is exported.
+    if options.cut_options_enabled {
+        let (start_time_seconds, _) = ffmpeg_utils::get_cut_range_in_seconds(options)?;
+        args.extend_from_slice(&[
+            "-ss".to_string(),
+            format!("{:.6}", start_time_seconds),
+            "-t".to_string(),
+            format!("{:.6}", output_length),
+        ]);
+    }
+
+    args.extend_from_slice(&["-i".to_string(), options.input_video_path.clone()]);
+
+    Ok((args, output_length))
+}
+
+fn get_animation_output_path(
+    options: &VideoEditOptions,
+    format: AnimationFormat,
+) -> Result<String, String> {
+    let input_path = Path::new(&options.input_video_path);
+    let output_path = Path::new(&options.output_video_path);
+
+    let new_file_name = match input_path.file_stem() {
+        Some(file_stem) => format!(
+            "{}_VideoCrop.{}",
+            file_stem.to_string_lossy(),
+            format.extension()
+        ),
+        None => format!("VideoCrop_{}.{}", Uuid::new_v4(), format.extension()),
+    };
+
+    ffmpeg_utils::resolve_output_path(&output_path.join(new_file_name))
+}
+
+fn export_gif(
+    input_args: &[String],
+    filters: &str,
+    animation_options: &AnimationExportOptions,
+    output_length: f64,
+    output_path: &str,
+) -> Result<(), String> {
+    let palette_path =
+        std::env::temp_dir().join(format!("VideoCrop_palette_{}.png", Uuid::new_v4()));
+
+    let mut palette_args = vec!["-y".to_string()];
+    palette_args.extend_from_slice(input_args);
+    palette_args.extend_from_slice(&[
+        "-vf".to_string(),
+        format!("{},palettegen=stats_mode=diff", filters),
+        "-an".to_string(),
+        "-frames:v".to_string(),
+        "1".to_string(),
+        palette_path.to_string_lossy().to_string(),
+    ]);
+
+    let result = ffmpeg_utils::run_ffmpeg_with_progress(&palette_args, output_length, (0.0, 50.0))
+        .and_then(|_| {
+            let mut gif_args = vec!["-y".to_string()];
+            gif_args.extend_from_slice(input_args);
+            gif_args.extend_from_slice(&[
+                "-i".to_string(),
+                palette_path.to_string_lossy().to_string(),
+                "-lavfi".to_string(),
+                format!(
+                    "{}[x];[x][1:v]paletteuse=dither={}:diff_mode=rectangle",
+                    filters,
+                    animation_options.dither.to_filter_value()
+                ),
+                "-an".to_string(),
+                "-loop".to_string(),
+                animation_options.loop_count.to_string(),
+                output_path.to_string(),
+            ]);
+
+            ffmpeg_utils::run_ffmpeg_with_progress(&gif_args, output_length, (50.0, 100.0))
+        });
+
+    if let Err(e) = fs::remove_file(&palette_path) {
+        eprintln!("Failed to remove palette file: {}", e);
+    }
+
+    result
+}
+
+fn run_animation_export(
+    options: &VideoEditOptions,
+    animation_options: &AnimationExportOptions,
+) -> Result<String, String> {
+    let (input_args, output_length) = get_input_args(options)?;
+    let filters = get_animation_filters(options, animation_options);
+    let output_path = get_animation_output_path(options, animation_options.format)?;
+
+    match animation_options.format {
+        AnimationFormat::Gif => export_gif(
+            &input_args,
+            &filters,
+            animation_options,
+            output_length,
+            &output_path,
+        )?,
+        AnimationFormat::Webp => {
+            let mut ffmpeg_args = vec!["-y".to_string()];
+            ffmpeg_args.extend(input_args);
+            ffmpeg_args.extend_from_slice(&[
+                "-vf".to_string(),
+                filters,
+                "-an".to_string(),
+                "-c:v".to_string(),
+                "libwebp".to_string(),
+                "-lossless".to_string(),
+                "0".to_string(),
+                "-q:v".to_string(),
+                animation_options.webp_quality.min(100).to_string(),
+                "-loop".to_string(),
+                animation_options.loop_count.to_string(),
+                output_path.clone(),
+            ]);
+            ffmpeg_utils::run_ffmpeg_with_progress(&ffmpeg_args, output_length, (0.0, 100.0))?
+        }
+        AnimationFormat::Apng => {
+            let mut ffmpeg_args = vec!["-y".to_string()];
+            ffmpeg_args.extend(input_args);
+            ffmpeg_args.extend_from_slice(&[
+                "-vf".to_string(),
+                filters,
+                "-an".to_string(),
+                "-c:v".to_string(),
+                "apng".to_string(),
+                "-plays".to_string(),
+                animation_options.loop_count.to_string(),
+                "-f".to_string(),
+                "apng".to_string(),
+                output_path.clone(),
+            ]);
+            ffmpeg_utils::run_ffmpeg_with_progress(&ffmpeg_args, output_length, (0.0, 100.0))?
+        }
+    }
+
+    Ok(output_path)
+}
+
+pub fn export_animation(
+    options: VideoEditOptions,
+    animation_options: AnimationExportOptions,
+) -> Result<String, String> {
+    ffmpeg_utils::start_video_progress();
+
+    let result = run_animation_export(&options, &animation_options);
+    ffmpeg_utils::finish_video_progress(&result);
+
+    result
+}
@@ -0,0 +1,15 @@
+use std::path::PathBuf;
+
+use tauri::{AppHandle, Manager};
+
+pub fn get_app_config_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_config_dir()
+        .map_err(|e| format!("Failed to resolve config directory: {}", e))
+}
+
+pub fn get_app_cache_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_cache_dir()
+        .map_err(|e| format!("Failed to resolve cache directory: {}", e))
+}
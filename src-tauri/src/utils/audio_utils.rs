@@ -0,0 +1,554 @@
+use serde::{Deserialize, Serialize};
+
+use crate::utils::ffmpeg_utils::{self, VideoEditOptions};
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AudioStreamInfo {
+    pub stream_index: u32,
+    pub audio_index: u32,
+    pub codec: String,
+    pub channels: u32,
+    pub channel_layout: Option<String>,
+    pub sample_rate: Option<u32>,
+    pub bit_rate: Option<u64>,
+    pub language: Option<String>,
+    pub title: Option<String>,
+    pub default: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AudioTrackMode {
+    #[default]
+    Default,
+    Keep,
+    Mix,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct AudioTrackOptions {
+    pub mode: AudioTrackMode,
+    pub selected_tracks: Vec<u32>,
+}
+
+pub struct AudioMapping {
+    pub args: Vec<String>,
+    pub filters_applied: bool,
+}
+
+pub fn get_string(value: &serde_json::Value, key: &str) -> Option<String> {
+    value
+        .get(key)
+        .and_then(|v| v.as_str())
+        .map(|v| v.to_string())
+}
+
+pub fn get_number<T: std::str::FromStr>(value: &serde_json::Value, key: &str) -> Option<T> {
+    match value.get(key)? {
+        serde_json::Value::String(v) => v.parse().ok(),
+        serde_json::Value::Number(v) => v.to_string().parse().ok(),
+        _ => None,
+    }
+}
+
+pub fn get_audio_streams(video_path: &str) -> Result<Vec<AudioStreamInfo>, String> {
+    let output_str = ffmpeg_utils::run_ffprobe(&[
+        "-v",
+        "error",
+        "-select_streams",
+        "a",
+        "-show_entries",
+        "stream=index,codec_name,channels,channel_layout,sample_rate,bit_rate:stream_tags=language,title:stream_disposition=default",
+        "-of",
+        "json",
+        video_path,
+    ])?;
+
+    let value: serde_json::Value = serde_json::from_str(&output_str)
+        .map_err(|e| format!("Failed to parse ffprobe output: {}", e))?;
+
+    let streams = value
+        .get("streams")
+        .and_then(|streams| streams.as_array())
+        .cloned()
+        .unwrap_or_default();
+
+    Ok(streams
+        .iter()
+        .enumerate()
+        .map(|(audio_index, stream)| {
+            let tags = stream.get("tags").cloned().unwrap_or_default();
+
+            AudioStreamInfo {
+                stream_index: get_number(stream, "index").unwrap_or(audio_index as u32),
+                audio_index: audio_index as u32,
+                codec: get_string(stream, "codec_name").unwrap_or_default(),
+                channels: get_number(stream, "channels").unwrap_or(0),
+                channel_layout: get_string(stream, "channel_layout"),
+                sample_rate: get_number(stream, "sample_rate"),
+                bit_rate: get_number(stream, "bit_rate"),
+                language: get_string(&tags, "language"),
+                title: get_string(&tags, "title"),
+                default: stream
+                    .get("disposition")
+                    .and_then(|disposition| get_number::<u32>(disposition, "default"))
+                    .is_some_and(|default| default == 1),
+            }
+        })
+        .collect())
+}
+
+pub fn get_mix_filter(selected_tracks: &[u32], audio_filters: &[String]) -> String {
+    let inputs: String = selected_tracks
+        .iter()
+        .map(|track| format!("[0:a:{}]", track))
+        .collect();
+
+    let mut filters = vec![format!(
+        "amix=inputs={}:duration=longest:normalize=0",
+        selected_tracks.len()
+    )];
+    filters.extend_from_slice(audio_filters);
+
+    format!("{}{}[aout]", inputs, filters.join(","))
+}
+
+fn get_filter_chain(input: &str, filters: &[String], output: &str) -> String {
+    if filters.is_empty() {
+        format!("{}anull{}", input, output)
+    } else {
+        format!("{}{}{}", input, filters.join(","), output)
+    }
+}
+
+// Streams coming out of -filter_complex cannot also be filtered with -af, so as soon as a
+// complex graph is needed (mixing or an external track) every audio filter moves into it.
+pub fn get_audio_mapping(
+    track_options: &AudioTrackOptions,
+    audio_filters: &[String],
+    external_audio: Option<(&ExternalAudioOptions, &[String])>,
+) -> AudioMapping {
+    if track_options.mode != AudioTrackMode::Mix && external_audio.is_none() {
+        let args = match track_options.mode {
+            AudioTrackMode::Keep => {
+                let mut args = vec!["-map".to_string(), "0:v:0?".to_string()];
+                for track in &track_options.selected_tracks {
+                    args.extend_from_slice(&["-map".to_string(), format!("0:a:{}", track)]);
+                }
+                args
+            }
+            _ => vec![],
+        };
+
+        return AudioMapping {
+            args,
+            filters_applied: false,
+        };
+    }
+
+    let mut graph: Vec<String> = Vec::new();
+    let mut args = vec!["-map".to_string(), "0:v:0?".to_string()];
+
+    let replace_original = external_audio
+        .is_some_and(|(external_options, _)| external_options.mode == ExternalAudioMode::Replace);
+
+    if !replace_original {
+        match track_options.mode {
+            AudioTrackMode::Default => {
+                graph.push(get_filter_chain("[0:a:0]", audio_filters, "[a0]"));
+                args.extend_from_slice(&["-map".to_string(), "[a0]".to_string()]);
+            }
+            AudioTrackMode::Keep => {
+                for track in &track_options.selected_tracks {
+                    graph.push(get_filter_chain(
+                        &format!("[0:a:{}]", track),
+                        audio_filters,
+                        &format!("[a{}]", track),
+                    ));
+                    args.extend_from_slice(&["-map".to_string(), format!("[a{}]", track)]);
+                }
+            }
+            AudioTrackMode::Mix => {
+                graph.push(get_mix_filter(
+                    &track_options.selected_tracks,
+                    audio_filters,
+                ));
+                args.extend_from_slice(&["-map".to_string(), "[aout]".to_string()]);
+            }
+        }
+    }
+
+    if let Some((external_options, external_filters)) = external_audio {
+        let mut filters = external_filters.to_vec();
+        filters.extend_from_slice(audio_filters);
+        graph.push(get_filter_chain("[1:a:0]", &filters, "[ext]"));
+        args.extend_from_slice(&["-map".to_string(), "[ext]".to_string()]);
+
+        if external_options.length_mode == ExternalAudioLengthMode::Shorten {
+            args.push("-shortest".to_string());
+        }
+    }
+
+    let mut mapping_args = vec!["-filter_complex".to_string(), graph.join(";")];
+    mapping_args.extend(args);
+
+    AudioMapping {
+        args: mapping_args,
+        filters_applied: true,
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExternalAudioMode {
+    #[default]
+    Replace,
+    Add,
+}
+
+#[derive(Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExternalAudioLengthMode {
+    #[default]
+    Pad,
+    Shorten,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExternalAudioOptions {
+    pub audio_path: String,
+    pub mode: ExternalAudioMode,
+    pub offset_seconds: f64,
+    pub trim_start_seconds: f64,
+    pub trim_end_seconds: Option<f64>,
+    pub length_mode: ExternalAudioLengthMode,
+}
+
+// Places the external audio on the timeline of the input video, so the regular cut and
+// silence removal apply to it the same way as to the original audio.
+pub fn get_external_audio_filters(
+    external_options: &ExternalAudioOptions,
+    video_length: f64,
+) -> Vec<String> {
+    let mut filters = Vec::new();
+
+    let trim_start =
+        external_options.trim_start_seconds.max(0.0) + (-external_options.offset_seconds).max(0.0);
+    match external_options.trim_end_seconds {
+        Some(trim_end) => {
+            filters.push(format!("atrim=start={:.6}:end={:.6}", trim_start, trim_end))
+        }
+        None if trim_start > 0.0 => filters.push(format!("atrim=start={:.6}", trim_start)),
+        None => {}
+    }
+    filters.push("asetpts=PTS-STARTPTS".to_string());
+
+    if external_options.offset_seconds > 0.0 {
+        filters.push(format!(
+            "adelay={}:all=1",
+            (external_options.offset_seconds * 1000.0).round() as u64
+        ));
+    }
+
+    if external_options.length_mode == ExternalAudioLengthMode::Pad {
+        filters.push(format!("apad=whole_dur={:.6}", video_length));
+    }
+    filters.push(format!("atrim=end={:.6}", video_length));
+
+    filters
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AudioLevelOptions {
+    pub normalize_enabled: bool,
+    pub target_lufs: f64,
+    pub true_peak_db: f64,
+    pub loudness_range: f64,
+    pub gain_db: f64,
+    pub fade_in_seconds: f64,
+    pub fade_out_seconds: f64,
+}
+
+impl Default for AudioLevelOptions {
+    fn default() -> Self {
+        AudioLevelOptions {
+            normalize_enabled: false,
+            target_lufs: -16.0,
+            true_peak_db: -1.5,
+            loudness_range: 11.0,
+            gain_db: 0.0,
+            fade_in_seconds: 0.0,
+            fade_out_seconds: 0.0,
+        }
+    }
+}
+
+impl AudioLevelOptions {
+    pub fn is_active(&self) -> bool {
+        self.normalize_enabled
+            || self.gain_db != 0.0
+            || self.fade_in_seconds > 0.0
+            || self.fade_out_seconds > 0.0
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct LoudnessMeasurement {
+    pub input_i: f64,
+    pub input_tp: f64,
+    pub input_lra: f64,
+    pub input_thresh: f64,
+    pub target_offset: f64,
+}
+
+fn get_loudnorm_filter(level_options: &AudioLevelOptions) -> String {
+    format!(
+        "loudnorm=I={:.1}:TP={:.1}:LRA={:.1}",
+        level_options.target_lufs, level_options.true_peak_db, level_options.loudness_range
+    )
+}
+
+fn parse_loudness_measurement(log: &str) -> Result<LoudnessMeasurement, String> {
+    let json_start = log.rfind('{').ok_or("Missing loudnorm measurement")?;
+    let json_end = log.rfind('}').ok_or("Missing loudnorm measurement")?;
+    if json_end < json_start {
+        return Err("Missing loudnorm measurement".to_string());
+    }
+
+    let value: serde_json::Value = serde_json::from_str(&log[json_start..=json_end])
+        .map_err(|e| format!("Failed to parse loudnorm measurement: {}", e))?;
+
+    let get_value = |key: &str| -> Result<f64, String> {
+        get_number::<f64>(&value, key)
+            .filter(|v| v.is_finite())
+            .ok_or(format!("Invalid loudnorm value for {}", key))
+    };
+
+    Ok(LoudnessMeasurement {
+        input_i: get_value("input_i")?,
+        input_tp: get_value("input_tp")?,
+        input_lra: get_value("input_lra")?,
+        input_thresh: get_value("input_thresh")?,
+        target_offset: get_value("target_offset")?,
+    })
+}
+
+// The first pass measures exactly the audio that ends up in the output, so the cut and the same
+// selection/mix filters are put in front of loudnorm. A replacing external track is measured
+// instead of the original audio.
+pub fn measure_loudness(
+    options: &VideoEditOptions,
+    pre_filters: &[String],
+    cut_range: Option<(f64, f64)>,
+    external_audio: Option<&[String]>,
+) -> Result<LoudnessMeasurement, String> {
+    let loudnorm_filter = format!(
+        "{}:print_format=json",
+        get_loudnorm_filter(&options.audio_level_options)
+    );
+
+    let mut args = vec!["-hide_banner".to_string()];
+
+    if let Some(external_filters) = external_audio {
+        let mut filters = external_filters.to_vec();
+        if let Some((start, length)) = cut_range {
+            filters.push(format!(
+                "atrim=start={:.6}:end={:.6}",
+                start,
+                start + length
+            ));
+        }
+        filters.extend_from_slice(pre_filters);
+        filters.push(loudnorm_filter);
+
+        args.extend_from_slice(&[
+            "-i".to_string(),
+            options.external_audio_options.audio_path.clone(),
+            "-filter_complex".to_string(),
+            format!("[0:a:0]{}[aout]", filters.join(",")),
+            "-map".to_string(),
+            "[aout]".to_string(),
+            "-f".to_string(),
+            "null".to_string(),
+            "-".to_string(),
+        ]);
+
+        let log = ffmpeg_utils::run_ffmpeg_for_log(&args)?;
+        return parse_loudness_measurement(&log);
+    }
+
+    if let Some((start, length)) = cut_range {
+        args.extend_from_slice(&[
+            "-ss".to_string(),
+            format!("{:.6}", start),
+            "-t".to_string(),
+            format!("{:.6}", length),
+        ]);
+    }
+
+    args.extend_from_slice(&[
+        "-i".to_string(),
+        options.input_video_path.clone(),
+        "-vn".to_string(),
+        "-sn".to_string(),
+        "-dn".to_string(),
+    ]);
+
+    let mut filters = pre_filters.to_vec();
+    filters.push(loudnorm_filter);
+
+    let track_options = &options.audio_track_options;
+    match track_options.mode {
+        AudioTrackMode::Mix => args.extend_from_slice(&[
+            "-filter_complex".to_string(),
+            get_mix_filter(&track_options.selected_tracks, &filters),
+            "-map".to_string(),
+            "[aout]".to_string(),
+        ]),
+        _ => {
+            if let (AudioTrackMode::Keep, Some(track)) =
+                (track_options.mode, track_options.selected_tracks.first())
+            {
+                args.extend_from_slice(&["-map".to_string(), format!("0:a:{}", track)]);
+            }
+            args.extend_from_slice(&["-af".to_string(), filters.join(",")]);
+        }
+    }
+
+    args.extend_from_slice(&["-f".to_string(), "null".to_string(), "-".to_string()]);
+
+    let log = ffmpeg_utils::run_ffmpeg_for_log(&args)?;
+    parse_loudness_measurement(&log)
+}
+
+pub fn get_level_filters(
+    level_options: &AudioLevelOptions,
+    measurement: Option<&LoudnessMeasurement>,
+    timeline_start: f64,
+    output_length: f64,
+    output_sample_rate: Option<u32>,
+) -> Vec<String> {
+    let mut filters = Vec::new();
+
+    if let Some(measurement) = measurement {
+        // loudnorm always outputs 192 kHz, so resample back to a regular rate afterwards.
+        filters.push(format!(
+            "{}:measured_I={:.2}:measured_TP={:.2}:measured_LRA={:.2}:measured_thresh={:.2}:offset={:.2}:linear=true",
+            get_loudnorm_filter(level_options),
+            measurement.input_i,
+            measurement.input_tp,
+            measurement.input_lra,
+            measurement.input_thresh,
+            measurement.target_offset
+        ));
+        filters.push(format!("aresample={}", output_sample_rate.unwrap_or(48000)));
+    }
+
+    if level_options.gain_db != 0.0 {
+        filters.push(format!("volume={:.2}dB", level_options.gain_db));
+    }
+
+    if level_options.fade_in_seconds > 0.0 {
+        filters.push(format!(
+            "afade=t=in:st={:.6}:d={:.6}",
+            timeline_start, level_options.fade_in_seconds
+        ));
+    }
+
+    if level_options.fade_out_seconds > 0.0 {
+        let fade_out_start =
+            timeline_start + (output_length - level_options.fade_out_seconds).max(0.0);
+        filters.push(format!(
+            "afade=t=out:st={:.6}:d={:.6}",
+            fade_out_start, level_options.fade_out_seconds
+        ));
+    }
+
+    filters
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AudioFormatOptions {
+    pub sample_rate: Option<u32>,
+    pub channel_layout: Option<String>,
+    pub pcm_bit_depth: u32,
+}
+
+impl Default for AudioFormatOptions {
+    fn default() -> Self {
+        AudioFormatOptions {
+            sample_rate: None,
+            channel_layout: None,
+            pcm_bit_depth: 16,
+        }
+    }
+}
+
+pub fn get_format_filters(format_options: &AudioFormatOptions) -> Vec<String> {
+    let mut filters = Vec::new();
+
+    if let Some(sample_rate) = format_options.sample_rate {
+        filters.push(format!("aresample={}", sample_rate));
+    }
+
+    if let Some(channel_layout) = &format_options.channel_layout {
+        filters.push(format!("aformat=channel_layouts={}", channel_layout));
+    }
+
+    filters
+}
+
+fn get_pcm_encoder(bit_depth: u32) -> &'static str {
+    match bit_depth {
+        24 => "pcm_s24le",
+        32 => "pcm_s32le",
+        _ => "pcm_s16le",
+    }
+}
+
+pub fn get_audio_encoder_args(
+    audio_codec: &str,
+    audio_bitrate: i32,
+    format_options: &AudioFormatOptions,
+) -> Vec<String> {
+    match audio_codec {
+        "copy" | "flac" | "alac" => vec!["-c:a".to_string(), audio_codec.to_string()],
+        "pcm" => vec![
+            "-c:a".to_string(),
+            get_pcm_encoder(format_options.pcm_bit_depth).to_string(),
+        ],
+        _ => {
+            let mut args = vec!["-c:a".to_string(), audio_codec.to_string()];
+            if audio_bitrate > 0 {
+                args.extend_from_slice(&["-b:a".to_string(), format!("{}k", audio_bitrate)]);
+            } else {
+                args.extend_from_slice(&["-q:a".to_string(), "0".to_string()]);
+            }
+            args
+        }
+    }
+}
+
+// Stream copies keep the source codec, so the container has to be picked from that instead.
+pub fn get_audio_extension(audio_codec: &str, source_codec: Option<&str>) -> &'static str {
+    let codec = if audio_codec == "copy" {
+        source_codec.unwrap_or_default()
+    } else {
+        audio_codec
+    };
+
+    match codec {
+        "aac" | "alac" => "m4a",
+        "libmp3lame" | "mp3" => "mp3",
+        "libopus" | "opus" => "opus",
+        "vorbis" | "libvorbis" => "ogg",
+        "flac" => "flac",
+        "ac3" => "ac3",
+        "eac3" => "eac3",
+        codec if codec == "pcm" || codec.starts_with("pcm_") => "wav",
+        _ => "mka",
+    }
+}
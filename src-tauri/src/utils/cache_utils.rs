@@ -0,0 +1,81 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+const CACHE_KEY_SAMPLE_BYTES: u64 = 64 * 1024;
+
+// Reads the head and tail of the file, so a file rewritten in place with the same size and
+// modification time still gets a new key.
+fn read_file_samples(file_path: &str, length: u64) -> Result<Vec<u8>, String> {
+    let mut file = fs::File::open(file_path).map_err(|e| format!("Failed to open file: {}", e))?;
+
+    let mut samples = Vec::new();
+    (&mut file)
+        .take(CACHE_KEY_SAMPLE_BYTES)
+        .read_to_end(&mut samples)
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+
+    if length > CACHE_KEY_SAMPLE_BYTES * 2 {
+        file.seek(SeekFrom::End(-(CACHE_KEY_SAMPLE_BYTES as i64)))
+            .map_err(|e| format!("Failed to seek file: {}", e))?;
+        file.take(CACHE_KEY_SAMPLE_BYTES)
+            .read_to_end(&mut samples)
+            .map_err(|e| format!("Failed to read file: {}", e))?;
+    } else if length > CACHE_KEY_SAMPLE_BYTES {
+        file.read_to_end(&mut samples)
+            .map_err(|e| format!("Failed to read file: {}", e))?;
+    }
+
+    Ok(samples)
+}
+
+pub fn get_file_cache_key(file_path: &str, variant: &str) -> Result<String, String> {
+    let metadata =
+        fs::metadata(file_path).map_err(|e| format!("Failed to read file metadata: {}", e))?;
+
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_millis())
+        .unwrap_or(0);
+
+    // Cache files outlive app updates, so the key uses SHA-256, whose output is fixed, rather than
+    // the std hasher, which may change between Rust releases. Fields are length prefixed so
+    // neighbouring values cannot run into each other.
+    let mut hasher = Sha256::new();
+    for field in [file_path.as_bytes(), variant.as_bytes()] {
+        hasher.update((field.len() as u64).to_le_bytes());
+        hasher.update(field);
+    }
+    hasher.update(metadata.len().to_le_bytes());
+    hasher.update(modified.to_le_bytes());
+    hasher.update(read_file_samples(file_path, metadata.len())?);
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+pub fn read_cached<T: DeserializeOwned>(cache_dir: &Path, file_name: &str) -> Option<T> {
+    let contents = fs::read_to_string(cache_dir.join(file_name)).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+pub fn write_cached<T: Serialize>(cache_dir: &Path, file_name: &str, value: &T) {
+    if let Err(e) = fs::create_dir_all(cache_dir) {
+        eprintln!("Failed to create cache directory: {}", e);
+        return;
+    }
+
+    match serde_json::to_string(value) {
+        Ok(contents) => {
+            if let Err(e) = fs::write(cache_dir.join(file_name), contents) {
+                eprintln!("Failed to write cache file {}: {}", file_name, e);
+            }
+        }
+        Err(e) => eprintln!("Failed to serialize cache file {}: {}", file_name, e),
+    }
+}
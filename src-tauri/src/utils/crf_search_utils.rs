@@ -0,0 +1,201 @@
+use serde::{Deserialize, Serialize};
+
+use crate::utils::ffmpeg_utils::{self, VideoEditOptions};
+use crate::utils::quality_utils::QualityMetric;
+use crate::utils::rate_control_utils::RateControlMode;
+use crate::utils::sample_utils;
+use crate::utils::validation_utils;
+
+fn default_target_score() -> f64 {
+    95.0
+}
+
+fn default_sample_count() -> u32 {
+    3
+}
+
+fn default_sample_seconds() -> f64 {
+    5.0
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CrfSearchOptions {
+    pub metric: QualityMetric,
+    #[serde(default = "default_target_score")]
+    pub target_score: f64,
+    #[serde(default = "default_sample_count")]
+    pub sample_count: u32,
+    #[serde(default = "default_sample_seconds")]
+    pub sample_seconds: f64,
+    #[serde(default)]
+    pub min_crf: Option<i32>,
+    #[serde(default)]
+    pub max_crf: Option<i32>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CrfSearchAttempt {
+    pub crf: i32,
+    pub score: f64,
+    pub projected_size_bytes: u64,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CrfSearchResult {
+    pub crf: i32,
+    pub score: f64,
+    pub target_met: bool,
+    pub projected_size_bytes: u64,
+    pub projected_size_mb: f64,
+    pub attempts: Vec<CrfSearchAttempt>,
+}
+
+// The useful part of each scale, CRFs outside of it are either visually lossless or unwatchable.
+pub fn get_default_search_range(codec: &str) -> Option<(i32, i32)> {
+    match codec {
+        "libx264" | "libx265" => Some((15, 40)),
+        "libsvtav1" | "libaom-av1" => Some((20, 55)),
+        _ => None,
+    }
+}
+
+pub fn get_search_range(
+    codec: &str,
+    search_options: &CrfSearchOptions,
+) -> Result<(i32, i32), String> {
+    let (codec_min, codec_max) = validation_utils::get_crf_range(codec)
+        .ok_or(format!("CRF search is not supported for {}", codec))?;
+    let (default_min, default_max) =
+        get_default_search_range(codec).unwrap_or((codec_min, codec_max));
+
+    Ok((
+        search_options.min_crf.unwrap_or(default_min).max(codec_min),
+        search_options.max_crf.unwrap_or(default_max).min(codec_max),
+    ))
+}
+
+fn run_attempt(
+    options: &VideoEditOptions,
+    search_options: &CrfSearchOptions,
+    ranges: &[(f64, f64)],
+    crf: i32,
+    output_length_seconds: f64,
+    progress_range: (f64, f64),
+) -> Result<CrfSearchAttempt, String> {
+    let mut attempt_options = options.clone();
+    attempt_options.compression_options.rate_control = RateControlMode::Crf;
+    attempt_options.compression_options.crf = crf;
+
+    let share = (progress_range.1 - progress_range.0) / ranges.len() as f64;
+    let mut samples = Vec::new();
+    let mut scores = Vec::new();
+
+    for (index, range) in ranges.iter().enumerate() {
+        let progress_start = progress_range.0 + share * index as f64;
+        let sample = sample_utils::encode_sample(
+            &attempt_options,
+            *range,
+            (progress_start, progress_start + share * 0.6),
+        )?;
+
+        let score = sample_utils::measure_sample(
+            &attempt_options,
+            &sample,
+            search_options.metric,
+            (progress_start + share * 0.6, progress_start + share),
+        )?
+        .ok_or("The quality comparison returned no score".to_string())?;
+
+        scores.push(score);
+        samples.push(sample);
+    }
+
+    Ok(CrfSearchAttempt {
+        crf,
+        score: scores.iter().sum::<f64>() / scores.len() as f64,
+        projected_size_bytes: sample_utils::get_projected_size_bytes(
+            &samples,
+            output_length_seconds,
+        ),
+    })
+}
+
+// A higher CRF always means a smaller file, so the search looks for the highest CRF that still
+// reaches the target.
+fn run_crf_search(
+    options: &VideoEditOptions,
+    search_options: &CrfSearchOptions,
+) -> Result<CrfSearchResult, String> {
+    let (mut low, mut high) = get_search_range(&options.compression_options.codec, search_options)?;
+    if low > high {
+        return Err("The minimum CRF must not be above the maximum CRF".to_string());
+    }
+
+    let ranges = sample_utils::get_sample_ranges(
+        options,
+        search_options.sample_count,
+        search_options.sample_seconds,
+    )?;
+    let output_length_seconds = ffmpeg_utils::get_output_length_in_seconds(options)?;
+
+    let max_attempts = ((high - low + 1) as f64).log2().ceil() as usize + 1;
+    let share = 100.0 / max_attempts as f64;
+
+    let mut attempts: Vec<CrfSearchAttempt> = Vec::new();
+    let mut best: Option<CrfSearchAttempt> = None;
+
+    while low <= high {
+        let crf = low + (high - low) / 2;
+        let progress_start = (share * attempts.len() as f64).min(100.0 - share);
+        let attempt = run_attempt(
+            options,
+            search_options,
+            &ranges,
+            crf,
+            output_length_seconds,
+            (progress_start, progress_start + share),
+        )?;
+
+        if attempt.score >= search_options.target_score {
+            best = Some(attempt.clone());
+            low = crf + 1;
+        } else {
+            high = crf - 1;
+        }
+        attempts.push(attempt);
+    }
+
+    let (best, target_met) = match best {
+        Some(best) => (best, true),
+        // Even the lowest CRF misses the target, it is still the closest there is.
+        None => (
+            attempts
+                .iter()
+                .min_by_key(|attempt| attempt.crf)
+                .cloned()
+                .ok_or("No CRF was tried".to_string())?,
+            false,
+        ),
+    };
+
+    Ok(CrfSearchResult {
+        crf: best.crf,
+        score: best.score,
+        target_met,
+        projected_size_bytes: best.projected_size_bytes,
+        projected_size_mb: best.projected_size_bytes as f64 / 1024.0 / 1024.0,
+        attempts,
+    })
+}
+
+pub fn search_crf(
+    options: &VideoEditOptions,
+    search_options: &CrfSearchOptions,
+) -> Result<CrfSearchResult, String> {
+    ffmpeg_utils::start_video_progress();
+
+    let result = run_crf_search(options, search_options);
+    ffmpeg_utils::finish_video_progress(&result);
+
+    result
+}
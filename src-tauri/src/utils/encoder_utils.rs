@@ -0,0 +1,303 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum CodecFamily {
+    X264,
+    X265,
+    Av1,
+    Other,
+}
+
+#[derive(Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PixelFormat {
+    #[default]
+    Auto,
+    Yuv420p,
+    Yuv420p10le,
+    Yuv422p,
+    Yuv422p10le,
+    Yuv444p,
+    Yuv444p10le,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct EncoderParam {
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AdvancedEncoderOptions {
+    pub pixel_format: PixelFormat,
+    pub tune: Option<String>,
+    pub profile: Option<String>,
+    pub keyframe_interval: Option<u32>,
+    pub faststart: bool,
+    pub codec_params: Vec<EncoderParam>,
+    pub extra_args: Vec<String>,
+}
+
+// Options the argument builder already sets, passing them again would either be ignored or
+// break the inputs, mapping and output of the command.
+// Option names without stream specifiers, so -c also covers -c:v and -codec:v:0.
+pub const RESERVED_EXTRA_ARGS: [&str; 31] = [
+    "-i",
+    "-y",
+    "-n",
+    "-f",
+    "-map",
+    "-map_metadata",
+    "-map_chapters",
+    "-ss",
+    "-to",
+    "-t",
+    "-vf",
+    "-af",
+    "-filter",
+    "-filter_complex",
+    "-filter_complex_script",
+    "-lavfi",
+    "-c",
+    "-codec",
+    "-vcodec",
+    "-acodec",
+    "-an",
+    "-vn",
+    "-b",
+    "-crf",
+    "-maxrate",
+    "-minrate",
+    "-bufsize",
+    "-pass",
+    "-passlogfile",
+    "-progress",
+    "-nostats",
+];
+
+pub fn get_codec_family(codec: &str) -> CodecFamily {
+    match codec {
+        "libx264" => CodecFamily::X264,
+        "libx265" => CodecFamily::X265,
+        "libsvtav1" | "libaom-av1" => CodecFamily::Av1,
+        _ => CodecFamily::Other,
+    }
+}
+
+impl PixelFormat {
+    pub fn as_str(&self) -> Option<&'static str> {
+        match self {
+            PixelFormat::Auto => None,
+            PixelFormat::Yuv420p => Some("yuv420p"),
+            PixelFormat::Yuv420p10le => Some("yuv420p10le"),
+            PixelFormat::Yuv422p => Some("yuv422p"),
+            PixelFormat::Yuv422p10le => Some("yuv422p10le"),
+            PixelFormat::Yuv444p => Some("yuv444p"),
+            PixelFormat::Yuv444p10le => Some("yuv444p10le"),
+        }
+    }
+}
+
+pub fn get_supported_pixel_formats(family: CodecFamily) -> &'static [PixelFormat] {
+    match family {
+        CodecFamily::X264 | CodecFamily::X265 => &[
+            PixelFormat::Yuv420p,
+            PixelFormat::Yuv420p10le,
+            PixelFormat::Yuv422p,
+            PixelFormat::Yuv422p10le,
+            PixelFormat::Yuv444p,
+            PixelFormat::Yuv444p10le,
+        ],
+        // SVT-AV1 only encodes 4:2:0.
+        CodecFamily::Av1 => &[PixelFormat::Yuv420p, PixelFormat::Yuv420p10le],
+        CodecFamily::Other => &[],
+    }
+}
+
+pub fn get_supported_tunes(family: CodecFamily) -> &'static [&'static str] {
+    match family {
+        CodecFamily::X264 => &[
+            "film",
+            "animation",
+            "grain",
+            "stillimage",
+            "fastdecode",
+            "zerolatency",
+            "psnr",
+            "ssim",
+        ],
+        CodecFamily::X265 => &[
+            "animation",
+            "grain",
+            "fastdecode",
+            "zerolatency",
+            "psnr",
+            "ssim",
+        ],
+        // SVT-AV1 takes its tune through svtav1-params.
+        CodecFamily::Av1 | CodecFamily::Other => &[],
+    }
+}
+
+pub fn get_supported_profiles(family: CodecFamily) -> &'static [&'static str] {
+    match family {
+        CodecFamily::X264 => &["baseline", "main", "high", "high10", "high422", "high444"],
+        CodecFamily::X265 => &[
+            "main",
+            "main10",
+            "main12",
+            "main422-10",
+            "main444-8",
+            "main444-10",
+        ],
+        CodecFamily::Av1 => &["main", "high", "professional"],
+        CodecFamily::Other => &[],
+    }
+}
+
+// The pixel formats a profile can carry, a mismatch makes the encoder fail at startup.
+pub fn get_profile_pixel_formats(family: CodecFamily, profile: &str) -> &'static [PixelFormat] {
+    match (family, profile) {
+        (CodecFamily::X264, "baseline" | "main" | "high") | (CodecFamily::X265, "main") => {
+            &[PixelFormat::Yuv420p]
+        }
+        (CodecFamily::X264, "high10")
+        | (CodecFamily::X265, "main10")
+        | (CodecFamily::Av1, "main") => &[PixelFormat::Yuv420p, PixelFormat::Yuv420p10le],
+        _ => get_supported_pixel_formats(family),
+    }
+}
+
+fn get_codec_params_option(codec: &str) -> Option<&'static str> {
+    match codec {
+        "libx264" => Some("-x264-params"),
+        "libx265" => Some("-x265-params"),
+        "libsvtav1" => Some("-svtav1-params"),
+        "libaom-av1" => Some("-aom-params"),
+        _ => None,
+    }
+}
+
+pub fn supports_codec_params(codec: &str) -> bool {
+    get_codec_params_option(codec).is_some()
+}
+
+pub fn is_valid_codec_param(param: &EncoderParam) -> bool {
+    let key_is_valid = !param.key.is_empty()
+        && param
+            .key
+            .chars()
+            .all(|character| character.is_ascii_alphanumeric() || "-_".contains(character));
+    let value_is_valid = !param.value.is_empty() && !param.value.contains([':', '=']);
+
+    key_is_valid && value_is_valid
+}
+
+pub fn is_extra_arg_option(arg: &str) -> bool {
+    arg.starts_with('-') && arg[1..].starts_with(|character: char| character.is_ascii_alphabetic())
+}
+
+pub fn is_reserved_extra_arg(arg: &str) -> bool {
+    let name = arg.split(':').next().unwrap_or(arg);
+    RESERVED_EXTRA_ARGS.contains(&name)
+}
+
+pub fn get_advanced_encoder_args(codec: &str, options: &AdvancedEncoderOptions) -> Vec<String> {
+    let mut args = Vec::new();
+
+    if let Some(pixel_format) = options.pixel_format.as_str() {
+        args.extend_from_slice(&["-pix_fmt".to_string(), pixel_format.to_string()]);
+    }
+
+    if let Some(tune) = &options.tune {
+        args.extend_from_slice(&["-tune".to_string(), tune.clone()]);
+    }
+
+    if let Some(profile) = &options.profile {
+        args.extend_from_slice(&["-profile:v".to_string(), profile.clone()]);
+    }
+
+    if let Some(keyframe_interval) = options.keyframe_interval {
+        args.extend_from_slice(&["-g".to_string(), keyframe_interval.to_string()]);
+    }
+
+    if let Some(params_option) = get_codec_params_option(codec) {
+        if !options.codec_params.is_empty() {
+            args.extend_from_slice(&[
+                params_option.to_string(),
+                options
+                    .codec_params
+                    .iter()
+                    .map(|param| format!("{}={}", param.key, param.value))
+                    .collect::<Vec<String>>()
+                    .join(":"),
+            ]);
+        }
+    }
+
+    args
+}
+
+// Muxer and free-form options go last, right before the output path.
+pub fn get_output_args(options: &AdvancedEncoderOptions) -> Vec<String> {
+    let mut args = Vec::new();
+
+    if options.faststart {
+        args.extend_from_slice(&["-movflags".to_string(), "+faststart".to_string()]);
+    }
+
+    args.extend(options.extra_args.iter().cloned());
+
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_reserved_extra_args_by_option_name() {
+        let cases = [
+            ("-c:v", true),
+            ("-c:v:0", true),
+            ("-codec:v:0", true),
+            ("-filter:v:0", true),
+            ("-filter_complex_script", true),
+            ("-map:0", true),
+            ("-to:0", true),
+            ("-b:v", true),
+            ("-b:a:1", true),
+            ("-crf", true),
+            ("-maxrate:v", true),
+            ("-pass", true),
+            ("-an", true),
+            ("-x264-params", false),
+            ("-g", false),
+            ("-bf", false),
+            ("-color_primaries", false),
+            ("-tag:v", false),
+        ];
+
+        for (arg, expected) in cases {
+            assert_eq!(is_reserved_extra_arg(arg), expected, "{}", arg);
+        }
+    }
+
+    #[test]
+    fn tells_options_from_values() {
+        let cases = [
+            ("-g", true),
+            ("-tag:v", true),
+            ("-1", false),
+            ("-0.5", false),
+            ("hvc1", false),
+            ("-", false),
+            ("", false),
+        ];
+
+        for (arg, expected) in cases {
+            assert_eq!(is_extra_arg_option(arg), expected, "{}", arg);
+        }
+    }
+}
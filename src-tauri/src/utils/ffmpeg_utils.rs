@@ -1,5 +1,6 @@
 use core::panic;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::env;
 use std::fs;
 use std::io::Read;
@@ -9,6 +10,22 @@ use std::path::Path;
 use std::{io::BufRead, process::Command, sync::Mutex};
 use uuid::Uuid;
 
+use crate::utils::analysis_utils;
+use crate::utils::audio_utils::{
+    self, AudioFormatOptions, AudioLevelOptions, AudioTrackMode, AudioTrackOptions,
+    ExternalAudioMode, ExternalAudioOptions, LoudnessMeasurement,
+};
+use crate::utils::encoder_utils::{self, AdvancedEncoderOptions};
+use crate::utils::frame_rate_utils::{self, FrameRateOptions};
+use crate::utils::metadata_utils::{self, MetadataOptions};
+use crate::utils::quality_utils::{self, QualityComparisonResult, QualityScores};
+use crate::utils::rate_control_utils::{self, BitrateUnit, EncodePass, RateControlMode};
+use crate::utils::resize_utils::{self, ResizeMode, ScalingAlgorithm};
+use crate::utils::settings_utils::{self, OverwritePolicy};
+use crate::utils::subtitle_utils::{self, SubtitleOptions};
+use crate::utils::timestamp_utils::{parse_frame_rate, Timestamp};
+use crate::utils::transform_utils::{self, TransformOptions};
+
 pub const FFMPEG_WIN_ARM64_ZIP_URL: &str =
     "https://github.com/Azmekk/VideoCrop/releases/download/FFmpeg-binaries/ffmpeg-win-arm64.zip";
 pub const FFMPEG_WIN_X86_ZIP_URL: &str =
@@ -18,9 +35,12 @@ pub const CREATE_NO_WINDOW: u32 = 0x08000000;
 
 #[derive(Clone, Serialize, Deserialize)]
 pub struct VideoInfo {
-    width: u32,
-    height: u32,
-    duration: String,
+    pub width: u32,
+    pub height: u32,
+    pub duration: Timestamp,
+    pub frame_rate: f64,
+    pub variable_frame_rate: bool,
+    pub rotation: u32,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -35,6 +55,8 @@ pub struct VideoEditProgress {
     progress: f64,
     working: bool,
     last_error: Option<String>,
+    loudness_measurement: Option<LoudnessMeasurement>,
+    quality_comparison: Option<QualityComparisonResult>,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -50,12 +72,36 @@ pub struct VideoEditOptions {
     pub resize_enabled: bool,
     pub resize_options: ResizeOptions,
     pub process_audio: bool,
+    #[serde(default)]
+    pub silence_removal_enabled: bool,
+    #[serde(default)]
+    pub silence_removal_options: SilenceRemovalOptions,
+    #[serde(default)]
+    pub audio_track_options: AudioTrackOptions,
+    #[serde(default)]
+    pub audio_level_options: AudioLevelOptions,
+    #[serde(default)]
+    pub audio_format_options: AudioFormatOptions,
+    #[serde(default)]
+    pub external_audio_enabled: bool,
+    #[serde(default)]
+    pub external_audio_options: ExternalAudioOptions,
+    #[serde(default)]
+    pub subtitle_options: SubtitleOptions,
+    #[serde(default)]
+    pub metadata_options: MetadataOptions,
+    #[serde(default)]
+    pub frame_rate_options: FrameRateOptions,
+    #[serde(default)]
+    pub transform_options: TransformOptions,
+    #[serde(default)]
+    pub measure_quality: bool,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
 pub struct VideoCutOptions {
-    pub starting_time_string: String,
-    pub end_time_string: String,
+    pub starting_time_string: Timestamp,
+    pub end_time_string: Timestamp,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -70,18 +116,49 @@ pub struct VideoCropPoints {
 pub struct VideoCompressionOptions {
     pub codec: String,
     pub preset: String,
-    pub using_crf: bool,
+    #[serde(default)]
+    pub rate_control: RateControlMode,
     pub crf: i32,
     pub bitrate: i32,
+    #[serde(default)]
+    pub max_bitrate: i32,
+    #[serde(default)]
+    pub buffer_size: Option<i32>,
+    #[serde(default)]
+    pub bitrate_unit: BitrateUnit,
     pub audio_codec: String,
     pub audio_bitrate: i32,
-    pub bitrate_type: i32,
+    #[serde(default)]
+    pub advanced_options: AdvancedEncoderOptions,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
 pub struct ResizeOptions {
     pub width: i32,
     pub height: i32,
+    #[serde(default)]
+    pub mode: ResizeMode,
+    #[serde(default)]
+    pub scaler: ScalingAlgorithm,
+    #[serde(default = "resize_utils::default_pad_color")]
+    pub pad_color: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SilenceRemovalOptions {
+    pub noise_threshold_db: f64,
+    pub min_silence_seconds: f64,
+    pub padding_seconds: f64,
+}
+
+impl Default for SilenceRemovalOptions {
+    fn default() -> Self {
+        SilenceRemovalOptions {
+            noise_threshold_db: -30.0,
+            min_silence_seconds: 1.0,
+            padding_seconds: 0.25,
+        }
+    }
 }
 
 lazy_static::lazy_static! {
@@ -89,8 +166,12 @@ lazy_static::lazy_static! {
         progress: 0.0,
         working: false,
         last_error: None,
+        loudness_measurement: None,
+        quality_comparison: None,
     });
 
+    static ref VIDEO_JOB_RUNNING: Mutex<bool> = Mutex::new(false);
+
     static ref FFMPEG_DOWNLOAD_PROGRESS: Mutex<DependenciesSetUpInfo> = Mutex::new(DependenciesSetUpInfo {
         percent_downloaded: 0.0,
         status: "".to_string(),
@@ -98,38 +179,7 @@ lazy_static::lazy_static! {
     });
 }
 
-fn convert_time_string_to_seconds(time_string: &str) -> Result<f64, String> {
-    let parts: Vec<&str> = time_string.split(':').collect();
-    if parts.len() != 3 {
-        return Err("Invalid time format".to_string());
-    }
-
-    let hours: f64 = parts[0]
-        .parse()
-        .map_err(|e| format!("Failed to parse hours: {}", e))?;
-    let minutes: f64 = parts[1]
-        .parse()
-        .map_err(|e| format!("Failed to parse minutes: {}", e))?;
-    let seconds: f64 = parts[2]
-        .parse()
-        .map_err(|e| format!("Failed to parse seconds: {}", e))?;
-
-    Ok(hours * 3600.0 + minutes * 60.0 + seconds)
-}
-
-pub fn get_video_info(video_path: &str) -> Result<VideoInfo, String> {
-    let args = [
-        "-v",
-        "error",
-        "-select_streams",
-        "v:0",
-        "-show_entries",
-        "stream=width,height,display_aspect_ratio",
-        "-of",
-        "default=noprint_wrappers=1:nokey=1",
-        video_path,
-    ];
-
+pub fn run_ffprobe(args: &[&str]) -> Result<String, String> {
     let command_str = format!(
         "{} {}",
         "ffprobe",
@@ -140,11 +190,11 @@ pub fn get_video_info(video_path: &str) -> Result<VideoInfo, String> {
     );
     println!("Executing command: {}", command_str);
 
-    let output = Command::new("ffprobe")
+    let output = Command::new(get_ffprobe_command())
         .args(args)
         .creation_flags(CREATE_NO_WINDOW)
         .output()
-        .map_err(|e| format!("Failed to execute ffprobe for width and height: {}", e))?;
+        .map_err(|e| format!("Failed to execute ffprobe: {}", e))?;
 
     if !output.status.success() {
         return Err(format!(
@@ -153,61 +203,112 @@ pub fn get_video_info(video_path: &str) -> Result<VideoInfo, String> {
         ));
     }
 
-    let output_str = match std::str::from_utf8(&output.stdout) {
-        Ok(str) => str,
-        Err(err) => return Err(err.to_string()),
-    };
+    String::from_utf8(output.stdout).map_err(|e| format!("Failed to parse ffprobe output: {}", e))
+}
+
+pub fn run_ffmpeg_for_log(args: &[String]) -> Result<String, String> {
+    let command_str = format!(
+        "{} {}",
+        "ffmpeg",
+        args.iter()
+            .map(|arg| format!("\"{}\"", arg))
+            .collect::<Vec<String>>()
+            .join(" ")
+    );
+    println!("Executing command: {}", command_str);
+
+    let output = Command::new(get_ffmpeg_command())
+        .args(args)
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    if !output.status.success() {
+        return Err(format!("ffmpeg failed with error: {}", stderr.trim()));
+    }
 
-    let mut lines = output_str.lines();
+    Ok(stderr)
+}
 
-    let width: u32 = lines
-        .next()
+pub fn get_video_info(video_path: &str) -> Result<VideoInfo, String> {
+    let output_str = run_ffprobe(&[
+        "-v",
+        "error",
+        "-select_streams",
+        "v:0",
+        "-show_entries",
+        "stream=width,height,avg_frame_rate,r_frame_rate:stream_tags=rotate:stream_side_data=rotation:format=duration",
+        "-of",
+        "default=noprint_wrappers=1",
+        video_path,
+    ])?;
+
+    let values: HashMap<&str, &str> = output_str
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .collect();
+
+    let width: u32 = values
+        .get("width")
         .ok_or("Missing width")?
         .parse()
         .map_err(|e| format!("Failed to parse width: {}", e))?;
 
-    let height: u32 = lines
-        .next()
+    let height: u32 = values
+        .get("height")
         .ok_or("Missing height")?
         .parse()
         .map_err(|e| format!("Failed to parse height: {}", e))?;
 
-    let duration_output = Command::new("ffprobe")
-        .creation_flags(CREATE_NO_WINDOW)
-        .args([
-            "-v",
-            "error",
-            "-show_entries",
-            "format=duration",
-            "-of",
-            "default=noprint_wrappers=1:nokey=1",
-            "-sexagesimal",
-            video_path,
-        ])
-        .output()
-        .map_err(|e| format!("Failed to execute ffprobe for duration: {}", e))?;
-
-    if !duration_output.status.success() {
-        return Err(format!(
-            "ffprobe for duration failed with error: {}",
-            std::str::from_utf8(&duration_output.stderr).unwrap_or("Unknown error")
-        ));
-    }
-
-    let duration = std::str::from_utf8(&duration_output.stdout)
-        .map_err(|e| format!("Failed to parse duration output: {}", e))?
-        .trim()
-        .to_string();
+    let duration: f64 = values
+        .get("duration")
+        .ok_or("Missing duration")?
+        .parse()
+        .map_err(|e| format!("Failed to parse duration: {}", e))?;
+
+    let average_frame_rate = values
+        .get("avg_frame_rate")
+        .and_then(|value| parse_frame_rate(value));
+    let base_frame_rate = values
+        .get("r_frame_rate")
+        .and_then(|value| parse_frame_rate(value));
+    let frame_rate = average_frame_rate.or(base_frame_rate).unwrap_or(0.0);
+    let variable_frame_rate =
+        frame_rate_utils::is_variable_frame_rate(frame_rate, base_frame_rate.unwrap_or(0.0));
+
+    // The display matrix stores a counter-clockwise angle, the older rotate tag a clockwise one.
+    let rotation_degrees = values
+        .get("rotation")
+        .and_then(|value| value.parse::<f64>().ok())
+        .map(|rotation| -rotation)
+        .or_else(|| {
+            values
+                .get("TAG:rotate")
+                .and_then(|value| value.parse::<f64>().ok())
+        })
+        .unwrap_or(0.0);
+    let rotation = ((rotation_degrees / 90.0).round() as i32 * 90).rem_euclid(360) as u32;
+
+    // Report the dimensions of the displayed frame, crop coordinates are picked on it.
+    let (width, height) = if rotation % 180 == 90 {
+        (height, width)
+    } else {
+        (width, height)
+    };
 
     Ok(VideoInfo {
         width,
         height,
-        duration,
+        duration: Timestamp::from_seconds(duration),
+        frame_rate,
+        variable_frame_rate,
+        rotation,
     })
 }
 
 pub fn get_video_length_in_seconds(video_path: &str) -> Result<f64, String> {
-    let duration_output = Command::new("ffprobe")
+    let duration_output = Command::new(get_ffprobe_command())
         .creation_flags(CREATE_NO_WINDOW)
         .args([
             "-v",
@@ -228,271 +329,729 @@ pub fn get_video_length_in_seconds(video_path: &str) -> Result<f64, String> {
         ));
     }
 
-    let duration = std::str::from_utf8(&duration_output.stdout)
+    let duration_str = std::str::from_utf8(&duration_output.stdout)
         .map_err(|e| format!("Failed to parse duration output: {}", e))?
-        .trim()
+        .trim();
+    let duration = duration_str
         .parse::<f64>()
-        .unwrap();
+        .map_err(|e| format!("Failed to parse duration '{}': {}", duration_str, e))?;
     Ok(duration)
 }
 
-pub fn process_video(options: VideoEditOptions) {
-    let mut progress = VIDEO_EDIT_PROGRESS.lock().unwrap();
-    progress.working = true;
-    progress.progress = 0.0;
-    progress.last_error = None;
-    drop(progress);
-
-    let mut ffmpeg_args = vec!["-i".to_string(), options.input_video_path.clone()];
+pub fn get_cut_range_in_seconds(options: &VideoEditOptions) -> Result<(f64, f64), String> {
+    let cut_options = &options.cut_options;
 
-    let mut video_length: f64 = get_video_length_in_seconds(&options.input_video_path).unwrap();
-
-    if options.cut_options_enabled {
-        let cut_options = &options.cut_options;
-        ffmpeg_args
-            .extend_from_slice(&["-ss".to_string(), cut_options.starting_time_string.clone()]);
-        ffmpeg_args.extend_from_slice(&["-to".to_string(), cut_options.end_time_string.clone()]);
+    let frame_rate = if cut_options.starting_time_string.needs_frame_rate()
+        || cut_options.end_time_string.needs_frame_rate()
+    {
+        Some(get_video_info(&options.input_video_path)?.frame_rate)
+    } else {
+        None
+    };
 
-        let start_time_seconds =
-            convert_time_string_to_seconds(&cut_options.starting_time_string).unwrap_or(0.0);
-        let end_time_seconds =
-            convert_time_string_to_seconds(&cut_options.end_time_string).unwrap_or(video_length);
-        video_length = end_time_seconds - start_time_seconds;
-    }
+    Ok((
+        cut_options.starting_time_string.to_seconds(frame_rate)?,
+        cut_options.end_time_string.to_seconds(frame_rate)?,
+    ))
+}
 
-    let video_length_in_us = (video_length * 1_000_000.0) as u64;
+// Crop coordinates are picked on the rotated and flipped frame, so the transform comes first.
+pub fn get_geometry_filters(options: &VideoEditOptions) -> Vec<String> {
+    let mut filters = transform_utils::get_transform_filters(&options.transform_options);
 
+    // 4:2:0 chroma needs even dimensions, odd crops are cut down by a pixel.
     if options.crop_enabled {
         let crop_options = &options.crop_options;
-        let crop_filter = format!(
+        filters.push(format!(
             "crop={}:{}:{}:{}",
-            crop_options.width,
-            crop_options.height,
+            crop_options.width - crop_options.width % 2,
+            crop_options.height - crop_options.height % 2,
             crop_options.starting_x_offset,
             crop_options.starting_y_offset
-        );
-        ffmpeg_args.extend_from_slice(&["-vf".to_string(), crop_filter]);
+        ));
     }
 
-    let compression_options = &options.compression_options;
-    if options.compression_enabled {
-        ffmpeg_args.extend_from_slice(&["-c:v".to_string(), compression_options.codec.clone()]);
-        ffmpeg_args.extend_from_slice(&["-preset".to_string(), compression_options.preset.clone()]);
+    if options.resize_enabled && !options.crop_enabled {
+        filters.extend(resize_utils::get_resize_filters(&options.resize_options));
+    }
 
-        if compression_options.using_crf {
-            ffmpeg_args
-                .extend_from_slice(&["-crf".to_string(), compression_options.crf.to_string()]);
-        } else {
-            ffmpeg_args.extend_from_slice(&[
-                "-b:v".to_string(),
-                format!(
-                    "{}{}",
-                    compression_options.bitrate,
-                    get_bitrate_type_from_int(compression_options.bitrate_type)
-                ),
-            ]);
+    filters
+}
+
+// Filter option values need ':' escaped and Windows separators would be read as escapes.
+pub fn escape_filter_path(path: &str) -> String {
+    format!(
+        "'{}'",
+        path.replace('\\', "/")
+            .replace(':', "\\:")
+            .replace('\'', "'\\\\\\''")
+    )
+}
+
+// Without a pass, two-pass ABR falls back to a single pass at the same average bitrate.
+pub fn get_video_encoder_args(
+    compression_options: &VideoCompressionOptions,
+    pass: Option<&EncodePass>,
+) -> Vec<String> {
+    let mut args = vec![
+        "-c:v".to_string(),
+        compression_options.codec.clone(),
+        "-preset".to_string(),
+        compression_options.preset.clone(),
+    ];
+
+    args.extend(rate_control_utils::get_rate_control_args(
+        compression_options,
+    ));
+
+    let mut advanced_options = compression_options.advanced_options.clone();
+    if let Some(pass) = pass {
+        let (pass_args, pass_params) =
+            rate_control_utils::get_pass_args(&compression_options.codec, pass);
+        args.extend(pass_args);
+        advanced_options.codec_params.extend(pass_params);
+    }
+
+    args.extend(encoder_utils::get_advanced_encoder_args(
+        &compression_options.codec,
+        &advanced_options,
+    ));
+
+    args
+}
+
+pub fn get_keep_segments_expression(segments: &[(f64, f64)]) -> String {
+    segments
+        .iter()
+        .map(|(start, end)| format!("between(t,{:.6},{:.6})", start, end))
+        .collect::<Vec<String>>()
+        .join("+")
+}
+
+fn get_audio_level_filters(
+    options: &VideoEditOptions,
+    audio_filters: &[String],
+    output_length: f64,
+    external_audio_filters: Option<&[String]>,
+) -> Result<Vec<String>, String> {
+    let level_options = &options.audio_level_options;
+
+    // Output seeking keeps the input timestamps in the filter graph, the silence removal
+    // select filters on the other hand restart the timeline at zero.
+    let cut_range = if options.cut_options_enabled && !options.silence_removal_enabled {
+        Some((get_cut_range_in_seconds(options)?.0, output_length))
+    } else {
+        None
+    };
+    let timeline_start = cut_range.map(|(start, _)| start).unwrap_or(0.0);
+
+    let measurement = if level_options.normalize_enabled {
+        let replacing_audio = external_audio_filters
+            .filter(|_| options.external_audio_options.mode == ExternalAudioMode::Replace);
+        let measurement =
+            audio_utils::measure_loudness(options, audio_filters, cut_range, replacing_audio)?;
+
+        let mut progress = VIDEO_EDIT_PROGRESS.lock().unwrap();
+        progress.loudness_measurement = Some(measurement.clone());
+        drop(progress);
+
+        Some(measurement)
+    } else {
+        None
+    };
+
+    Ok(audio_utils::get_level_filters(
+        level_options,
+        measurement.as_ref(),
+        timeline_start,
+        output_length,
+        options.audio_format_options.sample_rate,
+    ))
+}
+
+pub fn get_output_length_in_seconds(options: &VideoEditOptions) -> Result<f64, String> {
+    let video_length = get_video_length_in_seconds(&options.input_video_path)?;
+
+    if !options.cut_options_enabled {
+        return Ok(video_length);
+    }
+
+    let (start_time_seconds, end_time_seconds) = get_cut_range_in_seconds(options)?;
+
+    Ok(end_time_seconds.min(video_length) - start_time_seconds)
+}
+
+pub fn process_video(options: VideoEditOptions) -> Result<String, String> {
+    start_video_progress();
+
+    let video_info = match get_video_info(&options.input_video_path) {
+        Ok(info) => info,
+        Err(err) => {
+            set_video_progress_error(&err);
+            return Err(err);
         }
+    };
+
+    // FFmpeg applies options to the next file named, so every input is added before any of the
+    // output options.
+    let mut input_args = vec!["-y".to_string()];
+    input_args.extend(metadata_utils::get_rotation_input_args(
+        &options.metadata_options,
+    ));
+    input_args.extend_from_slice(&["-i".to_string(), options.input_video_path.clone()]);
+    let mut output_args: Vec<String> = Vec::new();
+
+    let mut select_filter: Option<String> = None;
+    let mut audio_filters: Vec<String> = Vec::new();
+
+    let mut video_length: f64 = match get_output_length_in_seconds(&options) {
+        Ok(length) => length,
+        Err(err) => {
+            set_video_progress_error(&err);
+            return Err(err);
+        }
+    };
+    let mut silence_removal_segments: Option<Vec<(f64, f64)>> = None;
+
+    if options.silence_removal_enabled {
+        // The cut range is already part of the kept segments, so -ss/-to are not needed here.
+        let segments = match analysis_utils::get_silence_removal_segments(&options) {
+            Ok(segments) => segments,
+            Err(err) => {
+                set_video_progress_error(&err);
+                return Err(err);
+            }
+        };
+
+        video_length = segments.iter().map(|(start, end)| end - start).sum();
+        select_filter = Some(format!(
+            "select='{}',setpts=N/FRAME_RATE/TB",
+            get_keep_segments_expression(&segments)
+        ));
+        audio_filters.push(format!(
+            "aselect='{}',asetpts=N/SR/TB",
+            get_keep_segments_expression(&segments)
+        ));
+        silence_removal_segments = Some(segments);
+    } else if options.cut_options_enabled {
+        let (start_time_seconds, end_time_seconds) = match get_cut_range_in_seconds(&options) {
+            Ok(range) => range,
+            Err(err) => {
+                set_video_progress_error(&err);
+                return Err(err);
+            }
+        };
+        output_args.extend_from_slice(&["-ss".to_string(), format!("{:.6}", start_time_seconds)]);
+        output_args.extend_from_slice(&["-to".to_string(), format!("{:.6}", end_time_seconds)]);
     }
 
-    if !options.process_audio {
-        ffmpeg_args.extend_from_slice(&["-an".to_string()]);
-    } else if options.compression_enabled {
-        ffmpeg_args
-            .extend_from_slice(&["-c:a".to_string(), compression_options.audio_codec.clone()]);
+    // The first pass only needs the video of the main input and the cut range.
+    let main_input_args = input_args.clone();
+    let cut_args = output_args.clone();
+    let mut next_input_index = 1;
+
+    let external_audio_filters = if options.external_audio_enabled {
+        let input_length = match get_video_length_in_seconds(&options.input_video_path) {
+            Ok(length) => length,
+            Err(err) => {
+                set_video_progress_error(&err);
+                return Err(err);
+            }
+        };
+
+        input_args.extend_from_slice(&[
+            "-i".to_string(),
+            options.external_audio_options.audio_path.clone(),
+        ]);
+        next_input_index += 1;
 
-        if compression_options.audio_codec != "copy" {
-            if compression_options.audio_bitrate == 0 {
-                ffmpeg_args.extend_from_slice(&["-q:a".to_string(), "0".to_string()]);
+        Some(audio_utils::get_external_audio_filters(
+            &options.external_audio_options,
+            input_length,
+        ))
+    } else {
+        None
+    };
+
+    let subtitle_input_index = next_input_index;
+    if options.subtitle_options.mode == subtitle_utils::SubtitleMode::SoftMux {
+        input_args.extend_from_slice(&[
+            "-i".to_string(),
+            options.subtitle_options.subtitle_path.clone(),
+        ]);
+        next_input_index += 1;
+    }
+
+    // Output seeking already moves the chapters of the input onto the cut range, only the
+    // silence removal timeline needs them remapped into a separate metadata input.
+    let mut chapters_path = None;
+    let chapters_input_index = match &silence_removal_segments {
+        _ if !options.metadata_options.keep_chapters => None,
+        Some(segments) => {
+            let chapters = match metadata_utils::get_chapters(&options.input_video_path) {
+                Ok(chapters) => metadata_utils::remap_chapters(&chapters, segments),
+                Err(err) => {
+                    set_video_progress_error(&err);
+                    return Err(err);
+                }
+            };
+
+            if chapters.is_empty() {
+                None
             } else {
-                ffmpeg_args.extend_from_slice(&[
-                    "-b:a".to_string(),
-                    format!("{}k", compression_options.audio_bitrate),
+                let path = match metadata_utils::write_chapters_file(&chapters) {
+                    Ok(path) => path,
+                    Err(err) => {
+                        set_video_progress_error(&err);
+                        return Err(err);
+                    }
+                };
+
+                input_args.extend_from_slice(&[
+                    "-f".to_string(),
+                    "ffmetadata".to_string(),
+                    "-i".to_string(),
+                    path.to_string_lossy().to_string(),
                 ]);
+                chapters_path = Some(path);
+                Some(next_input_index)
+            }
+        }
+        None => Some(0),
+    };
+
+    if options.process_audio && options.audio_level_options.is_active() {
+        match get_audio_level_filters(
+            &options,
+            &audio_filters,
+            video_length,
+            external_audio_filters.as_deref(),
+        ) {
+            Ok(level_filters) => audio_filters.extend(level_filters),
+            Err(err) => {
+                set_video_progress_error(&err);
+                return Err(err);
             }
         }
     }
 
-    if options.resize_enabled && !options.crop_enabled {
-        let resize_options = &options.resize_options;
+    if options.process_audio {
+        audio_filters.extend(audio_utils::get_format_filters(
+            &options.audio_format_options,
+        ));
+    }
 
-        let width = if resize_options.width % 2 == 0 {
-            resize_options.width
-        } else {
-            resize_options.width + 1
-        };
-        let height = if resize_options.height % 2 == 0 {
-            resize_options.height
+    let compression_options = &options.compression_options;
+    let pass_log_path = (options.compression_enabled
+        && compression_options.rate_control == RateControlMode::TwoPassAbr)
+        .then(rate_control_utils::get_pass_log_path);
+    if options.compression_enabled {
+        output_args.extend(get_video_encoder_args(
+            compression_options,
+            pass_log_path
+                .as_deref()
+                .map(|log_path| EncodePass {
+                    number: 2,
+                    log_path,
+                })
+                .as_ref(),
+        ));
+    }
+
+    if !options.process_audio {
+        output_args.extend_from_slice(&["-an".to_string()]);
+    } else if options.compression_enabled {
+        // Filtered or mixed audio cannot be stream copied.
+        let audio_is_filtered = !audio_filters.is_empty()
+            || options.audio_track_options.mode == AudioTrackMode::Mix
+            || options.external_audio_enabled;
+        let audio_codec = if compression_options.audio_codec == "copy" && audio_is_filtered {
+            "aac".to_string()
         } else {
-            resize_options.height + 1
+            compression_options.audio_codec.clone()
         };
 
-        ffmpeg_args.extend_from_slice(&["-vf".to_string(), format!("scale={}:{}", width, height)]);
+        output_args.extend(audio_utils::get_audio_encoder_args(
+            &audio_codec,
+            compression_options.audio_bitrate,
+            &options.audio_format_options,
+        ));
     }
 
-    ffmpeg_args.extend_from_slice(&[
-        "-progress".to_string(),
-        "-".to_string(),
-        "-loglevel".to_string(),
-        "error".to_string(),
-    ]);
+    // Subtitles are burned in after crop and scale so they stay inside the visible frame and are
+    // rendered at the output resolution, but before select so they still see the input timestamps.
+    let mut video_filters = get_geometry_filters(&metadata_utils::map_geometry_to_stored_frame(
+        &options,
+        &video_info,
+    ));
+    video_filters.extend(subtitle_utils::get_burn_in_filter(&options));
+    video_filters.extend(select_filter);
+    video_filters.extend(frame_rate_utils::get_frame_rate_filter(
+        &options.frame_rate_options,
+        video_info.frame_rate,
+    ));
+
+    if !video_filters.is_empty() {
+        output_args.extend_from_slice(&["-vf".to_string(), video_filters.join(",")]);
+    }
+
+    let mut mapping_args: Vec<String> = Vec::new();
+
+    if options.process_audio {
+        let audio_mapping = audio_utils::get_audio_mapping(
+            &options.audio_track_options,
+            &audio_filters,
+            external_audio_filters
+                .as_deref()
+                .map(|filters| (&options.external_audio_options, filters)),
+        );
+        mapping_args.extend(audio_mapping.args);
+
+        if !audio_mapping.filters_applied && !audio_filters.is_empty() {
+            mapping_args.extend_from_slice(&["-af".to_string(), audio_filters.join(",")]);
+        }
+    }
+
+    let subtitle_args = match subtitle_utils::get_subtitle_args(&options, subtitle_input_index) {
+        Ok(args) => args,
+        Err(err) => {
+            set_video_progress_error(&err);
+            return Err(err);
+        }
+    };
+
+    // Mapping any stream explicitly turns off the automatic selection of the others.
+    if subtitle_args.iter().any(|arg| arg == "-map")
+        && !mapping_args.iter().any(|arg| arg == "-map")
+    {
+        mapping_args.extend_from_slice(&[
+            "-map".to_string(),
+            "0:v:0?".to_string(),
+            "-map".to_string(),
+            "0:a:0?".to_string(),
+        ]);
+    }
+    output_args.extend(mapping_args);
+    output_args.extend(subtitle_args);
+    output_args.extend(metadata_utils::get_metadata_args(
+        &options.metadata_options,
+        chapters_input_index,
+    ));
+
+    if options.compression_enabled {
+        output_args.extend(encoder_utils::get_output_args(
+            &compression_options.advanced_options,
+        ));
+    }
 
     let input_path = std::path::Path::new(&options.input_video_path);
     let output_path = std::path::Path::new(&options.output_video_path);
 
-    if let Some(file_stem) = input_path.file_stem() {
+    let resolved_output_path = if let Some(file_stem) = input_path.file_stem() {
         let new_file_name = format!("{}_VideoCrop.{}", file_stem.to_string_lossy(), "mp4");
 
-        let new_output_path = get_unique_filename(&output_path.join(new_file_name));
-        ffmpeg_args.push(new_output_path);
+        resolve_output_path(&output_path.join(new_file_name))
     } else {
         let random_guid = Uuid::new_v4().to_string();
-        let random_output_path =
-            get_unique_filename(&output_path.join(format!("VideoCrop_{}.mp4", random_guid)));
+        resolve_output_path(&output_path.join(format!("VideoCrop_{}.mp4", random_guid)))
+    };
 
-        ffmpeg_args.push(random_output_path);
-    }
+    let final_output_path = match resolved_output_path {
+        Ok(path) => path,
+        Err(err) => {
+            set_video_progress_error(&err);
+            return Err(err);
+        }
+    };
+    let mut ffmpeg_args = input_args;
+    ffmpeg_args.extend(output_args);
+    ffmpeg_args.push(final_output_path.clone());
+
+    // The comparison decodes both videos again, so it gets a large share of the progress bar.
+    let encode_progress_end = if options.measure_quality { 60.0 } else { 100.0 };
+    let result = match &pass_log_path {
+        Some(log_path) => {
+            let mut first_pass_args = main_input_args;
+            first_pass_args.extend(cut_args);
+            if !video_filters.is_empty() {
+                first_pass_args.extend_from_slice(&["-vf".to_string(), video_filters.join(",")]);
+            }
+            first_pass_args.extend(get_video_encoder_args(
+                compression_options,
+                Some(&EncodePass {
+                    number: 1,
+                    log_path,
+                }),
+            ));
+            first_pass_args.extend_from_slice(&[
+                "-an".to_string(),
+                "-sn".to_string(),
+                "-dn".to_string(),
+                "-f".to_string(),
+                "null".to_string(),
+                "-".to_string(),
+            ]);
 
-    let command_str = format!(
-        "{} {}",
-        "ffmpeg",
-        ffmpeg_args
-            .iter()
-            .map(|arg| format!("\"{}\"", arg))
-            .collect::<Vec<String>>()
-            .join(" ")
-    );
-    println!("Executing command: {}", command_str);
+            let first_pass_end = encode_progress_end * 0.4;
+            run_ffmpeg_with_progress(&first_pass_args, video_length, (0.0, first_pass_end))
+                .and_then(|_| {
+                    run_ffmpeg_with_progress(
+                        &ffmpeg_args,
+                        video_length,
+                        (first_pass_end, encode_progress_end),
+                    )
+                })
+        }
+        None => run_ffmpeg_with_progress(&ffmpeg_args, video_length, (0.0, encode_progress_end)),
+    };
 
-    let mut child = Command::new("ffmpeg")
-        .args(&ffmpeg_args)
-        .stdout(std::process::Stdio::piped())
-        .stderr(std::process::Stdio::piped())
-        .creation_flags(CREATE_NO_WINDOW)
-        .spawn()
-        .expect("Failed to start ffmpeg process");
+    // The export itself succeeded, so a failed measurement is only reported as a warning.
+    if result.is_ok() && options.measure_quality {
+        let comparison = quality_utils::compare_quality(
+            &options,
+            &final_output_path,
+            None,
+            (encode_progress_end, 100.0),
+        )
+        .unwrap_or_else(|err| {
+            eprintln!("Failed to measure output quality: {}", err);
+            QualityComparisonResult {
+                scores: QualityScores::default(),
+                segments: Vec::new(),
+                vmaf_available: quality_utils::is_libvmaf_available(),
+                warnings: vec![format!("Failed to measure output quality: {}", err)],
+            }
+        });
 
-    let stdout = child.stdout.take().expect("Failed to capture stdout");
+        let mut progress = VIDEO_EDIT_PROGRESS.lock().unwrap();
+        progress.quality_comparison = Some(comparison);
+        drop(progress);
+    }
 
-    let stdout_reader = std::io::BufReader::new(stdout);
+    if let Some(log_path) = pass_log_path {
+        rate_control_utils::remove_pass_log_files(&log_path);
+    }
+    if let Some(chapters_path) = chapters_path {
+        if let Err(e) = fs::remove_file(&chapters_path) {
+            eprintln!("Failed to remove chapters file: {}", e);
+        }
+    }
+    finish_video_progress(&result);
 
-    let stdout_thread = std::thread::spawn(move || {
-        for line in stdout_reader.lines() {
-            let line = line.unwrap();
-            if !line.starts_with("out_time_us=") {
-                continue;
-            }
+    result.map(|_| final_output_path)
+}
 
-            let value = match line.split('=').nth(1) {
-                Some(v) => v,
-                None => continue,
-            };
+pub fn extract_audio(options: VideoEditOptions) {
+    start_video_progress();
 
-            let out_time_us = match value.parse::<u64>() {
-                Ok(v) => v,
-                Err(_) => continue,
-            };
+    let mut ffmpeg_args = vec![
+        "-y".to_string(),
+        "-i".to_string(),
+        options.input_video_path.clone(),
+    ];
 
-            let mut progress = VIDEO_EDIT_PROGRESS.lock().unwrap();
-            progress.progress = ((out_time_us as f64 / video_length_in_us as f64) * 100.0).round();
+    let mut output_args: Vec<String> = Vec::new();
+    let mut audio_filters: Vec<String> = Vec::new();
 
-            if progress.progress >= 100.0 {
-                progress.progress = 99.0;
-            }
-            drop(progress);
+    let mut video_length: f64 = match get_output_length_in_seconds(&options) {
+        Ok(length) => length,
+        Err(err) => {
+            set_video_progress_error(&err);
+            return;
         }
-    });
-
-    let status = child.wait().expect("Failed to wait on ffmpeg process");
-
-    stdout_thread.join().expect("Failed to join stdout thread");
+    };
 
-    if !status.success() {
-        eprintln!("ffmpeg process failed with status: {}", status.clone());
+    if options.silence_removal_enabled {
+        let segments = match analysis_utils::get_silence_removal_segments(&options) {
+            Ok(segments) => segments,
+            Err(err) => {
+                set_video_progress_error(&err);
+                return;
+            }
+        };
 
-        let mut progress = VIDEO_EDIT_PROGRESS.lock().unwrap();
-        progress.working = false;
-        progress.progress = 0.0;
-        progress.last_error = Some(status.to_string());
-        drop(progress);
+        video_length = segments.iter().map(|(start, end)| end - start).sum();
+        audio_filters.push(format!(
+            "aselect='{}',asetpts=N/SR/TB",
+            get_keep_segments_expression(&segments)
+        ));
+    } else if options.cut_options_enabled {
+        let (start_time_seconds, end_time_seconds) = match get_cut_range_in_seconds(&options) {
+            Ok(range) => range,
+            Err(err) => {
+                set_video_progress_error(&err);
+                return;
+            }
+        };
+        output_args.extend_from_slice(&["-ss".to_string(), format!("{:.6}", start_time_seconds)]);
+        output_args.extend_from_slice(&["-to".to_string(), format!("{:.6}", end_time_seconds)]);
     }
 
-    let mut progress = VIDEO_EDIT_PROGRESS.lock().unwrap();
-    if progress.last_error.is_none() {
-        progress.working = false;
-        progress.progress = 100.0;
-        progress.last_error = None;
+    if options.audio_level_options.is_active() {
+        match get_audio_level_filters(&options, &audio_filters, video_length, None) {
+            Ok(level_filters) => audio_filters.extend(level_filters),
+            Err(err) => {
+                set_video_progress_error(&err);
+                return;
+            }
+        }
     }
-    drop(progress);
 
-    std::thread::spawn(move || {
-        std::thread::sleep(std::time::Duration::from_secs(1));
-        clear_video_progress();
-    });
-}
+    audio_filters.extend(audio_utils::get_format_filters(
+        &options.audio_format_options,
+    ));
 
-pub fn extract_audio(mut options: VideoEditOptions) {
-    if !options.compression_enabled || options.compression_options.audio_codec == "copy" {
-        options.compression_options.audio_codec = "libmp3lame".to_string();
-        options.compression_options.audio_bitrate = 192;
-    }
+    let track_options = &options.audio_track_options;
 
-    let mut ffmpeg_args = vec!["-i".to_string(), options.input_video_path.clone()];
+    // Without compression the original audio is kept as is, or stored losslessly when it has to
+    // be filtered.
+    let audio_is_filtered = !audio_filters.is_empty() || track_options.mode == AudioTrackMode::Mix;
+    let (audio_codec, audio_bitrate) = if options.compression_enabled {
+        (
+            options.compression_options.audio_codec.clone(),
+            options.compression_options.audio_bitrate,
+        )
+    } else {
+        ("copy".to_string(), 0)
+    };
+    let audio_codec = if audio_codec == "copy" && audio_is_filtered {
+        "flac".to_string()
+    } else {
+        audio_codec
+    };
 
-    let mut video_length: f64 = get_video_length_in_seconds(&options.input_video_path).unwrap();
+    output_args.push("-vn".to_string());
+    output_args.extend(audio_utils::get_audio_encoder_args(
+        &audio_codec,
+        audio_bitrate,
+        &options.audio_format_options,
+    ));
+
+    let (default_track, source_codecs) = if audio_codec == "copy" {
+        match audio_utils::get_audio_streams(&options.input_video_path) {
+            Ok(streams) if !streams.is_empty() => (
+                streams
+                    .iter()
+                    .position(|stream| stream.default)
+                    .unwrap_or(0) as u32,
+                streams
+                    .into_iter()
+                    .map(|stream| stream.codec)
+                    .collect::<Vec<String>>(),
+            ),
+            Ok(_) => {
+                set_video_progress_error("Video has no audio tracks");
+                return;
+            }
+            Err(err) => {
+                set_video_progress_error(&err);
+                return;
+            }
+        }
+    } else {
+        (0, Vec::new())
+    };
+    let get_extension = |track: u32| {
+        audio_utils::get_audio_extension(
+            &audio_codec,
+            source_codecs
+                .get(track as usize)
+                .map(|codec| codec.as_str()),
+        )
+    };
 
-    if options.cut_options_enabled {
-        let cut_options = &options.cut_options;
-        ffmpeg_args
-            .extend_from_slice(&["-ss".to_string(), cut_options.starting_time_string.clone()]);
-        ffmpeg_args.extend_from_slice(&["-to".to_string(), cut_options.end_time_string.clone()]);
+    let input_path = std::path::Path::new(&options.input_video_path);
+    let output_path = std::path::Path::new(&options.output_video_path);
 
-        let start_time_seconds =
-            convert_time_string_to_seconds(&cut_options.starting_time_string).unwrap_or(0.0);
-        let end_time_seconds =
-            convert_time_string_to_seconds(&cut_options.end_time_string).unwrap_or(video_length);
-        video_length = end_time_seconds - start_time_seconds;
-    }
+    let file_stem = match input_path.file_stem() {
+        Some(file_stem) => file_stem.to_string_lossy().to_string(),
+        None => {
+            set_video_progress_error("Failed to get file stem for audio extraction");
+            return;
+        }
+    };
 
-    let video_length_in_us = (video_length * 1_000_000.0) as u64;
+    // Audio only containers hold a single stream, so kept tracks are written to separate files.
+    let outputs: Vec<(Vec<String>, String)> = match track_options.mode {
+        AudioTrackMode::Default => {
+            // Copies are mapped explicitly so the container matches the copied track.
+            let map_args = if audio_codec == "copy" {
+                vec!["-map".to_string(), format!("0:a:{}", default_track)]
+            } else {
+                vec![]
+            };
 
-    ffmpeg_args.extend_from_slice(&[
-        "-c:a".to_string(),
-        options.compression_options.audio_codec.clone(),
-    ]);
+            vec![(
+                map_args,
+                format!(
+                    "{}_VideoCrop_Audio.{}",
+                    file_stem,
+                    get_extension(default_track)
+                ),
+            )]
+        }
+        AudioTrackMode::Keep => track_options
+            .selected_tracks
+            .iter()
+            .map(|track| {
+                (
+                    vec!["-map".to_string(), format!("0:a:{}", track)],
+                    format!(
+                        "{}_VideoCrop_Audio_{}.{}",
+                        file_stem,
+                        track + 1,
+                        get_extension(*track)
+                    ),
+                )
+            })
+            .collect(),
+        AudioTrackMode::Mix => {
+            ffmpeg_args.extend_from_slice(&[
+                "-filter_complex".to_string(),
+                audio_utils::get_mix_filter(&track_options.selected_tracks, &audio_filters),
+            ]);
+            audio_filters.clear();
 
-    if options.compression_enabled && options.compression_options.audio_bitrate > 0 {
-        ffmpeg_args.extend_from_slice(&[
-            "-b:a".to_string(),
-            format!("{}k", options.compression_options.audio_bitrate.clone()),
-        ]);
-    } else {
-        ffmpeg_args.extend_from_slice(&["-q:a".to_string(), "0".to_string()]);
+            vec![(
+                vec!["-map".to_string(), "[aout]".to_string()],
+                format!("{}_VideoCrop_Audio.{}", file_stem, get_extension(0)),
+            )]
+        }
+    };
+
+    for (map_args, file_name) in outputs {
+        ffmpeg_args.extend(map_args);
+        ffmpeg_args.extend_from_slice(&output_args);
+
+        if !audio_filters.is_empty() {
+            ffmpeg_args.extend_from_slice(&["-af".to_string(), audio_filters.join(",")]);
+        }
+
+        match resolve_output_path(&output_path.join(file_name)) {
+            Ok(new_output_path) => ffmpeg_args.push(new_output_path),
+            Err(err) => {
+                set_video_progress_error(&err);
+                return;
+            }
+        }
     }
 
-    ffmpeg_args.extend_from_slice(&["-map".to_string(), "a".to_string()]);
+    let result = run_ffmpeg_with_progress(&ffmpeg_args, video_length, (0.0, 100.0));
+    finish_video_progress(&result);
+}
 
-    ffmpeg_args.extend_from_slice(&[
+pub fn run_ffmpeg_with_progress(
+    args: &[String],
+    output_length_seconds: f64,
+    progress_range: (f64, f64),
+) -> Result<(), String> {
+    let mut ffmpeg_args = vec![
         "-progress".to_string(),
         "-".to_string(),
         "-loglevel".to_string(),
         "error".to_string(),
-    ]);
-
-    let input_path = std::path::Path::new(&options.input_video_path);
-    let output_path = std::path::Path::new(&options.output_video_path);
-
-    if let Some(file_stem) = input_path.file_stem() {
-        let new_file_name = format!(
-            "{}_VideoCrop_Audio.{}",
-            file_stem.to_string_lossy(),
-            get_audio_extension_based_on_codec(&options.compression_options.audio_codec)
-        );
-
-        let new_output_path = get_unique_filename(&output_path.join(new_file_name));
-        ffmpeg_args.push(new_output_path);
-    } else {
-        panic!("Failed to get file stem for audio extraction");
-    }
+    ];
+    ffmpeg_args.extend_from_slice(args);
 
     let command_str = format!(
         "{} {}",
@@ -505,60 +1064,69 @@ pub fn extract_audio(mut options: VideoEditOptions) {
     );
     println!("Executing command: {}", command_str);
 
-    let mut child = Command::new("ffmpeg")
+    let mut child = Command::new(get_ffmpeg_command())
         .args(&ffmpeg_args)
         .stdout(std::process::Stdio::piped())
         .stderr(std::process::Stdio::piped())
         .creation_flags(CREATE_NO_WINDOW)
         .spawn()
-        .expect("Failed to start ffmpeg process");
+        .map_err(|e| format!("Failed to start ffmpeg process: {}", e))?;
 
     let stdout = child.stdout.take().expect("Failed to capture stdout");
 
     let stdout_reader = std::io::BufReader::new(stdout);
+    let output_length_in_us = (output_length_seconds * 1_000_000.0).max(1.0);
+    let (progress_start, progress_end) = progress_range;
 
     let stdout_thread = std::thread::spawn(move || {
-        for line in stdout_reader.lines() {
-            if let Ok(line) = line {
-                if line.starts_with("out_time_us=") {
-                    if let Some(value) = line.split('=').nth(1) {
-                        if let Ok(out_time_us) = value.parse::<u64>() {
-                            let mut progress = VIDEO_EDIT_PROGRESS.lock().unwrap();
-                            progress.progress =
-                                ((out_time_us as f64 / video_length_in_us as f64) * 100.0).round();
-
-                            if progress.progress >= 100.0 {
-                                progress.progress = 99.0;
-                            }
-                            drop(progress);
-                        }
-                    }
-                }
+        for line in stdout_reader.lines().map_while(Result::ok) {
+            if !line.starts_with("out_time_us=") {
+                continue;
+            }
+
+            let out_time_us = match line.split('=').nth(1).map(|v| v.parse::<u64>()) {
+                Some(Ok(v)) => v,
+                _ => continue,
+            };
+
+            let fraction = (out_time_us as f64 / output_length_in_us).min(1.0);
+
+            let mut progress = VIDEO_EDIT_PROGRESS.lock().unwrap();
+            progress.progress =
+                (progress_start + fraction * (progress_end - progress_start)).round();
+
+            if progress.progress >= 100.0 {
+                progress.progress = 99.0;
             }
+            drop(progress);
         }
     });
 
-    let status = child.wait().expect("Failed to wait on ffmpeg process");
+    let status = child
+        .wait()
+        .map_err(|e| format!("Failed to wait on ffmpeg process: {}", e))?;
 
     stdout_thread.join().expect("Failed to join stdout thread");
 
     if !status.success() {
-        eprintln!("ffmpeg process failed with status: {}", status.clone());
-
-        let mut progress = VIDEO_EDIT_PROGRESS.lock().unwrap();
-        progress.working = false;
-        progress.progress = 0.0;
-        progress.last_error = Some(status.to_string());
-        drop(progress);
+        eprintln!("ffmpeg process failed with status: {}", status);
+        return Err(status.to_string());
     }
 
-    let mut progress = VIDEO_EDIT_PROGRESS.lock().unwrap();
-    if progress.last_error.is_none() {
-        progress.working = false;
-        progress.progress = 100.0;
-        progress.last_error = None;
+    Ok(())
+}
+
+pub fn finish_video_progress<T>(result: &Result<T, String>) {
+    match result {
+        Ok(_) => {
+            let mut progress = VIDEO_EDIT_PROGRESS.lock().unwrap();
+            progress.working = false;
+            progress.progress = 100.0;
+            progress.last_error = None;
+            drop(progress);
+        }
+        Err(err) => set_video_progress_error(err),
     }
-    drop(progress);
 
     std::thread::spawn(move || {
         std::thread::sleep(std::time::Duration::from_secs(1));
@@ -566,16 +1134,7 @@ pub fn extract_audio(mut options: VideoEditOptions) {
     });
 }
 
-fn get_audio_extension_based_on_codec(codec: &str) -> &str {
-    match codec {
-        "aac" => "m4a",
-        "libmp3lame" => "mp3",
-        "libopus" => "opus",
-        _ => "mp3",
-    }
-}
-
-fn get_unique_filename(path: &Path) -> String {
+pub fn get_unique_filename(path: &Path) -> String {
     let mut unique_path = path.to_path_buf();
     let mut counter = 1;
 
@@ -590,6 +1149,79 @@ fn get_unique_filename(path: &Path) -> String {
     unique_path.to_string_lossy().to_string()
 }
 
+pub fn resolve_output_path(path: &Path) -> Result<String, String> {
+    match settings_utils::get_settings().overwrite_policy {
+        OverwritePolicy::Rename => Ok(get_unique_filename(path)),
+        OverwritePolicy::Overwrite => Ok(path.to_string_lossy().to_string()),
+        OverwritePolicy::Skip => {
+            if path.exists() {
+                Err(format!(
+                    "Output file already exists: {}",
+                    path.to_string_lossy()
+                ))
+            } else {
+                Ok(path.to_string_lossy().to_string())
+            }
+        }
+    }
+}
+
+pub fn get_ffmpeg_command() -> String {
+    get_dependency_command("ffmpeg")
+}
+
+pub fn get_ffprobe_command() -> String {
+    get_dependency_command("ffprobe")
+}
+
+fn get_dependency_command(name: &str) -> String {
+    match settings_utils::get_settings().ffmpeg_path_override {
+        Some(ffmpeg_dir) => Path::new(&ffmpeg_dir)
+            .join(name)
+            .to_string_lossy()
+            .to_string(),
+        None => name.to_string(),
+    }
+}
+
+// Every job reports through the single video edit progress, so only one may run at a time. The
+// guard is released when dropped, including on the thread the job is moved to.
+pub struct VideoJobGuard;
+
+impl Drop for VideoJobGuard {
+    fn drop(&mut self) {
+        *VIDEO_JOB_RUNNING.lock().unwrap() = false;
+    }
+}
+
+pub fn try_start_video_job() -> Result<VideoJobGuard, String> {
+    let mut running = VIDEO_JOB_RUNNING.lock().unwrap();
+    if *running {
+        return Err("Another export is still running".to_string());
+    }
+
+    *running = true;
+    Ok(VideoJobGuard)
+}
+
+pub fn start_video_progress() {
+    let mut progress = VIDEO_EDIT_PROGRESS.lock().unwrap();
+    progress.working = true;
+    progress.progress = 0.0;
+    progress.last_error = None;
+    progress.loudness_measurement = None;
+    progress.quality_comparison = None;
+    drop(progress);
+}
+
+pub fn set_video_progress_error(err: &str) {
+    let mut progress = VIDEO_EDIT_PROGRESS.lock().unwrap();
+    progress.working = false;
+    progress.progress = 0.0;
+    progress.last_error = Some(err.to_string());
+    drop(progress);
+}
+
 pub fn clear_video_progress() {
     let mut progress = VIDEO_EDIT_PROGRESS.lock().unwrap();
     progress.progress = 0.0;
@@ -760,11 +1392,3 @@ pub fn add_ffmpeg_to_app_env_if_it_exists() -> bool {
 
     false
 }
-
-pub fn get_bitrate_type_from_int(passed_type: i32) -> String {
-    match passed_type {
-        1 => "k".to_string(),
-        2 => "M".to_string(),
-        _ => "k".to_string(),
-    }
-}
@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+
+const STANDARD_FRAME_RATES: [f64; 15] = [
+    24000.0 / 1001.0,
+    24.0,
+    25.0,
+    30000.0 / 1001.0,
+    30.0,
+    48.0,
+    50.0,
+    60000.0 / 1001.0,
+    60.0,
+    90.0,
+    100.0,
+    120000.0 / 1001.0,
+    120.0,
+    144.0,
+    240.0,
+];
+// Variable frame rate recordings average a little below their nominal rate, 29.87 for a 29.97 fps
+// phone video, so averages this close to a standard rate are taken as that rate.
+const STANDARD_FRAME_RATE_TOLERANCE: f64 = 0.02;
+
+#[derive(Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FrameRateMode {
+    #[default]
+    Source,
+    Constant,
+    Half,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FrameRateOptions {
+    pub mode: FrameRateMode,
+    // Without a rate, constant mode keeps the source rate, snapped to the nearest standard one.
+    pub frame_rate: Option<f64>,
+}
+
+impl FrameRateOptions {
+    pub fn is_active(&self) -> bool {
+        self.mode != FrameRateMode::Source
+    }
+}
+
+// r_frame_rate is the base rate every timestamp fits on, for constant streams it matches the
+// average rate or, for interlaced ones, doubles it.
+pub fn is_variable_frame_rate(average_frame_rate: f64, base_frame_rate: f64) -> bool {
+    if average_frame_rate <= 0.0 || base_frame_rate <= 0.0 {
+        return false;
+    }
+
+    let ratio = base_frame_rate / average_frame_rate;
+    (ratio - 1.0).abs() > 0.01 && (ratio - 2.0).abs() > 0.02
+}
+
+pub fn snap_to_standard_frame_rate(frame_rate: f64) -> f64 {
+    STANDARD_FRAME_RATES
+        .iter()
+        .copied()
+        .min_by(|a, b| (a - frame_rate).abs().total_cmp(&(b - frame_rate).abs()))
+        .filter(|standard| {
+            (standard - frame_rate).abs() <= standard * STANDARD_FRAME_RATE_TOLERANCE
+        })
+        .unwrap_or(frame_rate)
+}
+
+pub fn get_output_frame_rate(options: &FrameRateOptions, source_frame_rate: f64) -> Option<f64> {
+    match options.mode {
+        FrameRateMode::Source => None,
+        FrameRateMode::Constant => Some(
+            options
+                .frame_rate
+                .unwrap_or_else(|| snap_to_standard_frame_rate(source_frame_rate)),
+        ),
+        FrameRateMode::Half => Some(snap_to_standard_frame_rate(source_frame_rate) / 2.0),
+    }
+}
+
+// The fps filter duplicates and drops frames onto an even grid, which also turns variable frame
+// rate sources into constant ones.
+pub fn get_frame_rate_filter(options: &FrameRateOptions, source_frame_rate: f64) -> Option<String> {
+    get_output_frame_rate(options, source_frame_rate)
+        .filter(|frame_rate| *frame_rate > 0.0)
+        .map(|frame_rate| format!("fps={:.6}", frame_rate))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snaps_to_standard_frame_rates() {
+        let cases = [
+            (29.87, 30000.0 / 1001.0),
+            (29.99, 30.0),
+            (59.6, 60000.0 / 1001.0),
+            (24.0, 24.0),
+            (25.2, 25.0),
+            (15.0, 15.0),
+            (37.5, 37.5),
+        ];
+
+        for (frame_rate, expected) in cases {
+            let snapped = snap_to_standard_frame_rate(frame_rate);
+            assert!(
+                (snapped - expected).abs() < 1e-9,
+                "{} -> {}",
+                frame_rate,
+                snapped
+            );
+        }
+    }
+
+    #[test]
+    fn halves_the_snapped_source_rate() {
+        let options = FrameRateOptions {
+            mode: FrameRateMode::Half,
+            frame_rate: None,
+        };
+
+        let half = get_output_frame_rate(&options, 59.7).unwrap();
+        assert!((half - 30000.0 / 1001.0).abs() < 1e-9, "{}", half);
+        assert_eq!(get_output_frame_rate(&options, 50.0), Some(25.0));
+    }
+}
@@ -0,0 +1,253 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::utils::cache_utils;
+use crate::utils::ffmpeg_utils::{self, VideoCropPoints};
+use crate::utils::timestamp_utils::Timestamp;
+use crate::utils::transform_utils::{self, TransformOptions};
+
+const DEFAULT_SPRITE_COLUMNS: u32 = 10;
+const DEFAULT_SPRITE_ROWS: u32 = 10;
+const DEFAULT_SPRITE_THUMBNAIL_WIDTH: u32 = 160;
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FrameImageFormat {
+    Png,
+    Jpeg,
+    Webp,
+}
+
+impl FrameImageFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            FrameImageFormat::Png => "png",
+            FrameImageFormat::Jpeg => "jpg",
+            FrameImageFormat::Webp => "webp",
+        }
+    }
+
+    fn quality_args(&self) -> Vec<String> {
+        match self {
+            FrameImageFormat::Png => vec![],
+            FrameImageFormat::Jpeg => vec!["-q:v".to_string(), "2".to_string()],
+            FrameImageFormat::Webp => vec!["-quality".to_string(), "90".to_string()],
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct FrameExtractionOptions {
+    pub input_video_path: String,
+    pub output_directory: String,
+    pub time: Timestamp,
+    pub format: FrameImageFormat,
+    #[serde(default)]
+    pub crop_options: Option<VideoCropPoints>,
+    #[serde(default)]
+    pub transform_options: TransformOptions,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SpriteSheetFrame {
+    pub index: u32,
+    pub time: Timestamp,
+    pub seconds: f64,
+    pub x: u32,
+    pub y: u32,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SpriteSheet {
+    pub image_path: String,
+    pub index_path: String,
+    pub columns: u32,
+    pub rows: u32,
+    pub thumbnail_width: u32,
+    pub thumbnail_height: u32,
+    pub interval_seconds: f64,
+    pub frames: Vec<SpriteSheetFrame>,
+}
+
+fn get_frame_file_name(video_path: &Path, seconds: f64, format: FrameImageFormat) -> String {
+    let file_stem = video_path.file_stem().unwrap_or_default().to_string_lossy();
+    let total_ms = (seconds * 1000.0).round() as u64;
+
+    format!(
+        "{}_frame_{:02}-{:02}-{:02}-{:03}.{}",
+        file_stem,
+        total_ms / 3_600_000,
+        (total_ms / 60_000) % 60,
+        (total_ms / 1000) % 60,
+        total_ms % 1000,
+        format.extension()
+    )
+}
+
+pub fn extract_frame(options: &FrameExtractionOptions) -> Result<String, String> {
+    let video_info = ffmpeg_utils::get_video_info(&options.input_video_path)?;
+    let duration = video_info.duration.to_seconds(None)?;
+    let seconds = options.time.to_seconds(Some(video_info.frame_rate))?;
+
+    if seconds > duration {
+        return Err(format!(
+            "Frame time {} is past the end of the video ({:.3}s)",
+            options.time, duration
+        ));
+    }
+
+    let output_directory = Path::new(&options.output_directory);
+    if !output_directory.is_dir() {
+        return Err(format!(
+            "Output directory does not exist: {}",
+            options.output_directory
+        ));
+    }
+
+    let output_path =
+        ffmpeg_utils::resolve_output_path(&output_directory.join(get_frame_file_name(
+            Path::new(&options.input_video_path),
+            seconds,
+            options.format,
+        )))?;
+
+    let mut args = vec![
+        "-hide_banner".to_string(),
+        "-y".to_string(),
+        "-ss".to_string(),
+        format!("{:.6}", seconds),
+        "-i".to_string(),
+        options.input_video_path.clone(),
+        "-frames:v".to_string(),
+        "1".to_string(),
+    ];
+
+    // Crop coordinates are picked on the rotated and flipped frame, so the transform comes first.
+    let mut filters = transform_utils::get_transform_filters(&options.transform_options);
+    if let Some(crop_options) = &options.crop_options {
+        filters.push(format!(
+            "crop={}:{}:{}:{}",
+            crop_options.width,
+            crop_options.height,
+            crop_options.starting_x_offset,
+            crop_options.starting_y_offset
+        ));
+    }
+
+    if !filters.is_empty() {
+        args.extend_from_slice(&["-vf".to_string(), filters.join(",")]);
+    }
+
+    args.extend(options.format.quality_args());
+    args.push(output_path.clone());
+
+    ffmpeg_utils::run_ffmpeg_for_log(&args)?;
+    Ok(output_path)
+}
+
+pub fn generate_sprite_sheet(
+    video_path: &str,
+    columns: Option<u32>,
+    rows: Option<u32>,
+    thumbnail_width: Option<u32>,
+    cache_dir: &Path,
+) -> Result<SpriteSheet, String> {
+    let columns = columns.unwrap_or(DEFAULT_SPRITE_COLUMNS).max(1);
+    let rows = rows.unwrap_or(DEFAULT_SPRITE_ROWS).max(1);
+    let thumbnail_width = thumbnail_width
+        .unwrap_or(DEFAULT_SPRITE_THUMBNAIL_WIDTH)
+        .max(2)
+        / 2
+        * 2;
+
+    let cache_key = cache_utils::get_file_cache_key(
+        video_path,
+        &format!("sprites:{}x{}:{}", columns, rows, thumbnail_width),
+    )?;
+    let image_file_name = format!("sprites_{}.jpg", cache_key);
+    let index_file_name = format!("sprites_{}.json", cache_key);
+
+    if let Some(cached) = cache_utils::read_cached::<SpriteSheet>(cache_dir, &index_file_name) {
+        if Path::new(&cached.image_path).exists() {
+            return Ok(cached);
+        }
+    }
+
+    let video_info = ffmpeg_utils::get_video_info(video_path)?;
+    let duration = video_info.duration.to_seconds(None)?;
+    if duration <= 0.0 || video_info.width == 0 {
+        return Err("Video has no duration or dimensions".to_string());
+    }
+
+    let thumbnail_height = ((thumbnail_width as f64 * video_info.height as f64
+        / video_info.width as f64
+        / 2.0)
+        .round() as u32
+        * 2)
+    .max(2);
+
+    // Sample in the middle of each interval so the first tile is not a black intro frame.
+    let frame_count = columns * rows;
+    let interval_seconds = duration / frame_count as f64;
+
+    std::fs::create_dir_all(cache_dir)
+        .map_err(|e| format!("Failed to create cache directory: {}", e))?;
+    let image_path = cache_dir.join(&image_file_name);
+
+    let args = vec![
+        "-hide_banner".to_string(),
+        "-y".to_string(),
+        "-i".to_string(),
+        video_path.to_string(),
+        "-an".to_string(),
+        "-sn".to_string(),
+        "-dn".to_string(),
+        "-vf".to_string(),
+        format!(
+            "fps=1/{:.6}:start_time={:.6},scale={}:{},tile={}x{}",
+            interval_seconds,
+            interval_seconds / 2.0,
+            thumbnail_width,
+            thumbnail_height,
+            columns,
+            rows
+        ),
+        "-frames:v".to_string(),
+        "1".to_string(),
+        "-q:v".to_string(),
+        "4".to_string(),
+        image_path.to_string_lossy().to_string(),
+    ];
+
+    ffmpeg_utils::run_ffmpeg_for_log(&args)?;
+
+    let frames = (0..frame_count)
+        .map(|index| {
+            let seconds = interval_seconds * (index as f64 + 0.5);
+            SpriteSheetFrame {
+                index,
+                time: Timestamp::from_seconds(seconds),
+                seconds,
+                x: (index % columns) * thumbnail_width,
+                y: (index / columns) * thumbnail_height,
+            }
+        })
+        .collect();
+
+    let sprite_sheet = SpriteSheet {
+        image_path: image_path.to_string_lossy().to_string(),
+        index_path: cache_dir
+            .join(&index_file_name)
+            .to_string_lossy()
+            .to_string(),
+        columns,
+        rows,
+        thumbnail_width,
+        thumbnail_height,
+        interval_seconds,
+        frames,
+    };
+    cache_utils::write_cached(cache_dir, &index_file_name, &sprite_sheet);
+
+    Ok(sprite_sheet)
+}
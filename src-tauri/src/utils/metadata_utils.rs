@@ -0,0 +1,240 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+use crate::utils::audio_utils::{get_number, get_string};
+use crate::utils::ffmpeg_utils::{self, VideoEditOptions, VideoInfo};
+use crate::utils::resize_utils::ResizeMode;
+
+#[derive(Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetadataMode {
+    #[default]
+    Preserve,
+    Strip,
+}
+
+#[derive(Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RotationMode {
+    #[default]
+    Transpose,
+    Keep,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct MetadataEntry {
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MetadataOptions {
+    pub mode: MetadataMode,
+    pub edits: Vec<MetadataEntry>,
+    pub keep_chapters: bool,
+    pub rotation_mode: RotationMode,
+}
+
+impl Default for MetadataOptions {
+    fn default() -> Self {
+        MetadataOptions {
+            mode: MetadataMode::Preserve,
+            edits: Vec::new(),
+            keep_chapters: true,
+            rotation_mode: RotationMode::Transpose,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Chapter {
+    pub start_seconds: f64,
+    pub end_seconds: f64,
+    pub title: Option<String>,
+}
+
+pub fn get_chapters(video_path: &str) -> Result<Vec<Chapter>, String> {
+    let output_str =
+        ffmpeg_utils::run_ffprobe(&["-v", "error", "-show_chapters", "-of", "json", video_path])?;
+
+    let value: serde_json::Value = serde_json::from_str(&output_str)
+        .map_err(|e| format!("Failed to parse ffprobe output: {}", e))?;
+
+    let chapters = value
+        .get("chapters")
+        .and_then(|chapters| chapters.as_array())
+        .cloned()
+        .unwrap_or_default();
+
+    Ok(chapters
+        .iter()
+        .filter_map(|chapter| {
+            let tags = chapter.get("tags").cloned().unwrap_or_default();
+
+            Some(Chapter {
+                start_seconds: get_number(chapter, "start_time")?,
+                end_seconds: get_number(chapter, "end_time")?,
+                title: get_string(&tags, "title"),
+            })
+        })
+        .collect())
+}
+
+// Moves each chapter onto the output timeline made of the kept segments, chapters that fall
+// completely outside of them are dropped.
+pub fn remap_chapters(chapters: &[Chapter], segments: &[(f64, f64)]) -> Vec<Chapter> {
+    chapters
+        .iter()
+        .filter_map(|chapter| {
+            let mut output_offset = 0.0;
+            let mut output_range: Option<(f64, f64)> = None;
+
+            for (segment_start, segment_end) in segments {
+                let start = chapter.start_seconds.max(*segment_start);
+                let end = chapter.end_seconds.min(*segment_end);
+
+                if start < end {
+                    let mapped_start = output_offset + start - segment_start;
+                    let mapped_end = output_offset + end - segment_start;
+                    output_range = Some(match output_range {
+                        Some((first_start, _)) => (first_start, mapped_end),
+                        None => (mapped_start, mapped_end),
+                    });
+                }
+
+                output_offset += segment_end - segment_start;
+            }
+
+            output_range.map(|(start_seconds, end_seconds)| Chapter {
+                start_seconds,
+                end_seconds,
+                title: chapter.title.clone(),
+            })
+        })
+        .collect()
+}
+
+fn escape_ffmetadata_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for character in value.chars() {
+        if matches!(character, '=' | ';' | '#' | '\\' | '\n') {
+            escaped.push('\\');
+        }
+        escaped.push(character);
+    }
+    escaped
+}
+
+pub fn write_chapters_file(chapters: &[Chapter]) -> Result<PathBuf, String> {
+    let mut contents = String::from(";FFMETADATA1\n");
+
+    for chapter in chapters {
+        contents.push_str(&format!(
+            "[CHAPTER]\nTIMEBASE=1/1000\nSTART={}\nEND={}\n",
+            (chapter.start_seconds * 1000.0).round() as i64,
+            (chapter.end_seconds * 1000.0).round() as i64
+        ));
+
+        if let Some(title) = &chapter.title {
+            contents.push_str(&format!("title={}\n", escape_ffmetadata_value(title)));
+        }
+    }
+
+    let chapters_path =
+        std::env::temp_dir().join(format!("VideoCrop_chapters_{}.txt", Uuid::new_v4()));
+    fs::write(&chapters_path, contents)
+        .map_err(|e| format!("Failed to write chapters file: {}", e))?;
+
+    Ok(chapters_path)
+}
+
+pub fn get_rotation_input_args(options: &MetadataOptions) -> Vec<String> {
+    // Without autorotation FFmpeg carries the display matrix over to the output stream.
+    match options.rotation_mode {
+        RotationMode::Keep => vec!["-noautorotate".to_string()],
+        RotationMode::Transpose => vec![],
+    }
+}
+
+pub fn get_metadata_args(
+    options: &MetadataOptions,
+    chapters_input_index: Option<usize>,
+) -> Vec<String> {
+    let mut args = match options.mode {
+        MetadataMode::Preserve => vec!["-map_metadata".to_string(), "0".to_string()],
+        MetadataMode::Strip => vec![
+            "-map_metadata".to_string(),
+            "-1".to_string(),
+            "-map_metadata:s".to_string(),
+            "-1".to_string(),
+        ],
+    };
+
+    // An empty value removes the field.
+    for entry in &options.edits {
+        args.extend_from_slice(&[
+            "-metadata".to_string(),
+            format!("{}={}", entry.key, entry.value),
+        ]);
+    }
+
+    match chapters_input_index {
+        Some(index) => args.extend_from_slice(&["-map_chapters".to_string(), index.to_string()]),
+        None => args.extend_from_slice(&["-map_chapters".to_string(), "-1".to_string()]),
+    }
+
+    args
+}
+
+// Crop and resize values are picked on the displayed frame, when the rotation is only kept as
+// metadata they have to be moved back onto the stored frame.
+pub fn map_geometry_to_stored_frame(
+    options: &VideoEditOptions,
+    video_info: &VideoInfo,
+) -> VideoEditOptions {
+    let mut stored_options = options.clone();
+    if options.metadata_options.rotation_mode != RotationMode::Keep || video_info.rotation == 0 {
+        return stored_options;
+    }
+
+    let display_width = video_info.width as i32;
+    let display_height = video_info.height as i32;
+    let crop = &options.crop_options;
+    let stored_crop = &mut stored_options.crop_options;
+
+    match video_info.rotation {
+        90 => {
+            stored_crop.starting_x_offset = crop.starting_y_offset;
+            stored_crop.starting_y_offset = display_width - crop.starting_x_offset - crop.width;
+            stored_crop.width = crop.height;
+            stored_crop.height = crop.width;
+        }
+        180 => {
+            stored_crop.starting_x_offset = display_width - crop.starting_x_offset - crop.width;
+            stored_crop.starting_y_offset = display_height - crop.starting_y_offset - crop.height;
+        }
+        270 => {
+            stored_crop.starting_x_offset = display_height - crop.starting_y_offset - crop.height;
+            stored_crop.starting_y_offset = crop.starting_x_offset;
+            stored_crop.width = crop.height;
+            stored_crop.height = crop.width;
+        }
+        _ => {}
+    }
+
+    if video_info.rotation % 180 == 90 {
+        let stored_resize = &mut stored_options.resize_options;
+        stored_resize.width = options.resize_options.height;
+        stored_resize.height = options.resize_options.width;
+        stored_resize.mode = match options.resize_options.mode {
+            ResizeMode::FitWidth => ResizeMode::FitHeight,
+            ResizeMode::FitHeight => ResizeMode::FitWidth,
+            mode => mode,
+        };
+    }
+
+    stored_options
+}
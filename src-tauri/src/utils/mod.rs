@@ -1 +1,23 @@
+pub mod analysis_utils;
+pub mod animation_utils;
+pub mod app_dir_utils;
+pub mod audio_utils;
+pub mod cache_utils;
+pub mod crf_search_utils;
+pub mod encoder_utils;
 pub mod ffmpeg_utils;
+pub mod frame_rate_utils;
+pub mod frame_utils;
+pub mod metadata_utils;
+pub mod preset_utils;
+pub mod quality_utils;
+pub mod rate_control_utils;
+pub mod resize_utils;
+pub mod sample_utils;
+pub mod sanitize_utils;
+pub mod settings_utils;
+pub mod subtitle_utils;
+pub mod timestamp_utils;
+pub mod transform_utils;
+pub mod validation_utils;
+pub mod watch_utils;
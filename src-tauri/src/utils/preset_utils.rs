@@ -0,0 +1,523 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::utils::audio_utils::{
+    AudioFormatOptions, AudioLevelOptions, AudioTrackOptions, ExternalAudioOptions,
+};
+use crate::utils::encoder_utils::{self, AdvancedEncoderOptions, PixelFormat};
+use crate::utils::ffmpeg_utils::{
+    self, ResizeOptions, SilenceRemovalOptions, VideoCompressionOptions, VideoCropPoints,
+    VideoCutOptions, VideoEditOptions,
+};
+use crate::utils::frame_rate_utils::FrameRateOptions;
+use crate::utils::metadata_utils::MetadataOptions;
+use crate::utils::rate_control_utils::{self, BitrateUnit, RateControlMode};
+use crate::utils::resize_utils::{self, ResizeMode, ScalingAlgorithm};
+use crate::utils::subtitle_utils::SubtitleOptions;
+use crate::utils::timestamp_utils::Timestamp;
+use crate::utils::transform_utils::TransformOptions;
+
+pub const PRESETS_FILE_NAME: &str = "presets.json";
+pub const CURRENT_PRESET_VERSION: u64 = 1;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct EditPresetSettings {
+    pub cut_options_enabled: bool,
+    pub cut_options: VideoCutOptions,
+    pub crop_enabled: bool,
+    pub crop_options: VideoCropPoints,
+    pub compression_enabled: bool,
+    pub compression_options: VideoCompressionOptions,
+    pub resize_enabled: bool,
+    pub resize_options: ResizeOptions,
+    pub process_audio: bool,
+    #[serde(default)]
+    pub silence_removal_enabled: bool,
+    #[serde(default)]
+    pub silence_removal_options: SilenceRemovalOptions,
+    #[serde(default)]
+    pub audio_track_options: AudioTrackOptions,
+    #[serde(default)]
+    pub audio_level_options: AudioLevelOptions,
+    #[serde(default)]
+    pub audio_format_options: AudioFormatOptions,
+    #[serde(default)]
+    pub metadata_options: MetadataOptions,
+    #[serde(default)]
+    pub frame_rate_options: FrameRateOptions,
+    #[serde(default)]
+    pub transform_options: TransformOptions,
+    #[serde(default)]
+    pub measure_quality: bool,
+    #[serde(default)]
+    pub target_file_size_mb: Option<f64>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct EditPreset {
+    pub name: String,
+    #[serde(default = "current_preset_version")]
+    pub version: u64,
+    #[serde(default)]
+    pub built_in: bool,
+    pub settings: EditPresetSettings,
+}
+
+fn current_preset_version() -> u64 {
+    CURRENT_PRESET_VERSION
+}
+
+fn built_in_preset(
+    name: &str,
+    compression_options: VideoCompressionOptions,
+    target_file_size_mb: Option<f64>,
+) -> EditPreset {
+    EditPreset {
+        name: name.to_string(),
+        version: CURRENT_PRESET_VERSION,
+        built_in: true,
+        settings: EditPresetSettings {
+            cut_options_enabled: false,
+            cut_options: VideoCutOptions {
+                starting_time_string: Timestamp::from_seconds(0.0),
+                end_time_string: Timestamp::from_seconds(0.0),
+            },
+            crop_enabled: false,
+            crop_options: VideoCropPoints {
+                starting_x_offset: 0,
+                starting_y_offset: 0,
+                width: 0,
+                height: 0,
+            },
+            compression_enabled: true,
+            compression_options,
+            resize_enabled: false,
+            resize_options: ResizeOptions {
+                width: 0,
+                height: 0,
+                mode: ResizeMode::Fit,
+                scaler: ScalingAlgorithm::Bicubic,
+                pad_color: resize_utils::default_pad_color(),
+            },
+            process_audio: true,
+            silence_removal_enabled: false,
+            silence_removal_options: SilenceRemovalOptions::default(),
+            audio_track_options: AudioTrackOptions::default(),
+            audio_level_options: AudioLevelOptions::default(),
+            audio_format_options: AudioFormatOptions::default(),
+            metadata_options: MetadataOptions::default(),
+            frame_rate_options: FrameRateOptions::default(),
+            transform_options: TransformOptions::default(),
+            measure_quality: false,
+            target_file_size_mb,
+        },
+    }
+}
+
+pub fn get_built_in_presets() -> Vec<EditPreset> {
+    vec![
+        built_in_preset(
+            "Discord 10MB",
+            VideoCompressionOptions {
+                codec: "libx264".to_string(),
+                preset: "medium".to_string(),
+                rate_control: RateControlMode::TwoPassAbr,
+                crf: 23,
+                bitrate: 1000,
+                max_bitrate: 0,
+                buffer_size: None,
+                bitrate_unit: BitrateUnit::Kbps,
+                audio_codec: "aac".to_string(),
+                audio_bitrate: 96,
+                advanced_options: AdvancedEncoderOptions {
+                    pixel_format: PixelFormat::Yuv420p,
+                    faststart: true,
+                    ..AdvancedEncoderOptions::default()
+                },
+            },
+            Some(10.0),
+        ),
+        built_in_preset(
+            "Archive HEVC",
+            VideoCompressionOptions {
+                codec: "libx265".to_string(),
+                preset: "slow".to_string(),
+                rate_control: RateControlMode::Crf,
+                crf: 20,
+                bitrate: 5550,
+                max_bitrate: 0,
+                buffer_size: None,
+                bitrate_unit: BitrateUnit::Kbps,
+                audio_codec: "copy".to_string(),
+                audio_bitrate: 0,
+                advanced_options: AdvancedEncoderOptions::default(),
+            },
+            None,
+        ),
+        built_in_preset(
+            "Web AV1",
+            VideoCompressionOptions {
+                codec: "libsvtav1".to_string(),
+                preset: "8".to_string(),
+                rate_control: RateControlMode::Crf,
+                crf: 35,
+                bitrate: 2500,
+                max_bitrate: 0,
+                buffer_size: None,
+                bitrate_unit: BitrateUnit::Kbps,
+                audio_codec: "libopus".to_string(),
+                audio_bitrate: 128,
+                advanced_options: AdvancedEncoderOptions {
+                    pixel_format: PixelFormat::Yuv420p,
+                    faststart: true,
+                    ..AdvancedEncoderOptions::default()
+                },
+            },
+            None,
+        ),
+    ]
+}
+
+fn migrate_preset(mut value: serde_json::Value) -> serde_json::Value {
+    let mut version = value.get("version").and_then(|v| v.as_u64()).unwrap_or(0);
+
+    while version < CURRENT_PRESET_VERSION {
+        // Unversioned presets predate the rate control modes, they stored a CRF flag, with a
+        // single pass average bitrate otherwise, and 1 or 2 for a kbps or Mbps bitrate. The
+        // bitrate is kept as a one pass constrained VBR capped at the same rate, so migrated
+        // presets do not take twice as long to encode.
+        if version == 0 {
+            if let Some(compression_options) = value
+                .get_mut("settings")
+                .and_then(|settings| settings.get_mut("compression_options"))
+                .and_then(|compression_options| compression_options.as_object_mut())
+            {
+                if let Some(using_crf) = compression_options.remove("using_crf") {
+                    if using_crf.as_bool().unwrap_or(true) {
+                        compression_options
+                            .entry("rate_control")
+                            .or_insert(serde_json::json!("crf"));
+                    } else {
+                        let bitrate = compression_options
+                            .get("bitrate")
+                            .cloned()
+                            .unwrap_or(serde_json::json!(0));
+                        compression_options
+                            .entry("rate_control")
+                            .or_insert(serde_json::json!("constrained_vbr"));
+                        compression_options.entry("max_bitrate").or_insert(bitrate);
+                    }
+                }
+
+                if let Some(bitrate_type) = compression_options.remove("bitrate_type") {
+                    let bitrate_unit = if bitrate_type.as_i64() == Some(2) {
+                        "mbps"
+                    } else {
+                        "kbps"
+                    };
+                    compression_options
+                        .entry("bitrate_unit")
+                        .or_insert(serde_json::json!(bitrate_unit));
+                }
+            }
+        }
+        version += 1;
+    }
+
+    value["version"] = serde_json::json!(version);
+    value
+}
+
+// Preset files hold a list of presets, exported single presets may also be a lone object.
+fn parse_presets(contents: &str) -> Result<Vec<EditPreset>, String> {
+    let values = match serde_json::from_str(contents).map_err(|e| e.to_string())? {
+        serde_json::Value::Array(values) => values,
+        value @ serde_json::Value::Object(_) => vec![value],
+        _ => return Err("root is not a preset or a list of presets".to_string()),
+    };
+
+    values
+        .into_iter()
+        .map(|value| {
+            // Migrations index into the preset, which only works on an object.
+            if !value.is_object() {
+                return Err("preset is not an object".to_string());
+            }
+
+            serde_json::from_value(migrate_preset(value)).map_err(|e| e.to_string())
+        })
+        .collect()
+}
+
+fn read_user_presets(config_dir: &Path) -> Result<Vec<EditPreset>, String> {
+    let presets_path = config_dir.join(PRESETS_FILE_NAME);
+    if !presets_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let contents = fs::read_to_string(&presets_path)
+        .map_err(|e| format!("Failed to read presets file: {}", e))?;
+
+    parse_presets(&contents).map_err(|e| format!("Failed to parse presets file: {}", e))
+}
+
+fn write_user_presets(config_dir: &Path, presets: &[EditPreset]) -> Result<(), String> {
+    fs::create_dir_all(config_dir)
+        .map_err(|e| format!("Failed to create config directory: {}", e))?;
+
+    let contents = serde_json::to_string_pretty(presets)
+        .map_err(|e| format!("Failed to serialize presets: {}", e))?;
+
+    fs::write(config_dir.join(PRESETS_FILE_NAME), contents)
+        .map_err(|e| format!("Failed to write presets file: {}", e))
+}
+
+fn is_built_in_preset_name(name: &str) -> bool {
+    get_built_in_presets()
+        .iter()
+        .any(|preset| preset.name.eq_ignore_ascii_case(name))
+}
+
+pub fn list_presets(config_dir: &Path) -> Result<Vec<EditPreset>, String> {
+    let mut presets = get_built_in_presets();
+    presets.extend(read_user_presets(config_dir)?);
+    Ok(presets)
+}
+
+pub fn get_preset(config_dir: &Path, name: &str) -> Result<EditPreset, String> {
+    list_presets(config_dir)?
+        .into_iter()
+        .find(|preset| preset.name == name)
+        .ok_or(format!("Preset not found: {}", name))
+}
+
+pub fn save_preset(config_dir: &Path, mut preset: EditPreset) -> Result<(), String> {
+    let name = preset.name.trim().to_string();
+    if name.is_empty() {
+        return Err("Preset name cannot be empty".to_string());
+    }
+
+    if is_built_in_preset_name(&name) {
+        return Err(format!("Cannot overwrite built-in preset: {}", name));
+    }
+
+    preset.name = name;
+    preset.version = CURRENT_PRESET_VERSION;
+    preset.built_in = false;
+
+    let mut presets = read_user_presets(config_dir)?;
+    match presets.iter_mut().find(|p| p.name == preset.name) {
+        Some(existing) => *existing = preset,
+        None => presets.push(preset),
+    }
+
+    write_user_presets(config_dir, &presets)
+}
+
+pub fn delete_preset(config_dir: &Path, name: &str) -> Result<(), String> {
+    if is_built_in_preset_name(name) {
+        return Err(format!("Cannot delete built-in preset: {}", name));
+    }
+
+    let mut presets = read_user_presets(config_dir)?;
+    let preset_count = presets.len();
+    presets.retain(|preset| preset.name != name);
+
+    if presets.len() == preset_count {
+        return Err(format!("Preset not found: {}", name));
+    }
+
+    write_user_presets(config_dir, &presets)
+}
+
+pub fn import_presets(config_dir: &Path, file_path: &Path) -> Result<Vec<String>, String> {
+    let contents =
+        fs::read_to_string(file_path).map_err(|e| format!("Failed to read preset file: {}", e))?;
+
+    let imported =
+        parse_presets(&contents).map_err(|e| format!("Failed to parse preset file: {}", e))?;
+
+    let mut imported_names = Vec::new();
+    for preset in imported {
+        if is_built_in_preset_name(&preset.name) {
+            println!("Skipping import of built-in preset: {}", preset.name);
+            continue;
+        }
+
+        imported_names.push(preset.name.clone());
+        save_preset(config_dir, preset)?;
+    }
+
+    Ok(imported_names)
+}
+
+pub fn export_presets(config_dir: &Path, names: &[String], file_path: &Path) -> Result<(), String> {
+    let presets: Vec<EditPreset> = list_presets(config_dir)?
+        .into_iter()
+        .filter(|preset| names.is_empty() || names.contains(&preset.name))
+        .map(|mut preset| {
+            preset.built_in = false;
+            preset
+        })
+        .collect();
+
+    if presets.is_empty() {
+        return Err("No presets to export".to_string());
+    }
+
+    let contents = serde_json::to_string_pretty(&presets)
+        .map_err(|e| format!("Failed to serialize presets: {}", e))?;
+
+    fs::write(file_path, contents).map_err(|e| format!("Failed to write preset file: {}", e))
+}
+
+pub fn apply_preset(
+    preset: &EditPreset,
+    input_video_path: &str,
+    output_video_path: &str,
+) -> Result<VideoEditOptions, String> {
+    let settings = preset.settings.clone();
+    let mut options = VideoEditOptions {
+        input_video_path: input_video_path.to_string(),
+        output_video_path: output_video_path.to_string(),
+        cut_options_enabled: settings.cut_options_enabled,
+        cut_options: settings.cut_options,
+        crop_enabled: settings.crop_enabled,
+        crop_options: settings.crop_options,
+        compression_enabled: settings.compression_enabled,
+        compression_options: settings.compression_options,
+        resize_enabled: settings.resize_enabled,
+        resize_options: settings.resize_options,
+        process_audio: settings.process_audio,
+        silence_removal_enabled: settings.silence_removal_enabled,
+        silence_removal_options: settings.silence_removal_options,
+        audio_track_options: settings.audio_track_options,
+        audio_level_options: settings.audio_level_options,
+        audio_format_options: settings.audio_format_options,
+        external_audio_enabled: false,
+        external_audio_options: ExternalAudioOptions::default(),
+        subtitle_options: SubtitleOptions::default(),
+        // Edited fields such as the title belong to a single video.
+        metadata_options: MetadataOptions {
+            edits: Vec::new(),
+            ..settings.metadata_options
+        },
+        frame_rate_options: settings.frame_rate_options,
+        transform_options: settings.transform_options,
+        measure_quality: settings.measure_quality,
+    };
+
+    if let Some(target_file_size_mb) = settings.target_file_size_mb {
+        let video_length = ffmpeg_utils::get_output_length_in_seconds(&options)?;
+        if video_length <= 0.0 {
+            return Err("Cannot fit a video with no length to a target size".to_string());
+        }
+
+        let audio_bitrate = if options.process_audio {
+            options.compression_options.audio_bitrate.max(0) as f64
+        } else {
+            0.0
+        };
+
+        // Leave a little headroom for container overhead so the output lands under the target.
+        let total_kbits = target_file_size_mb * 8.0 * 1024.0 * 0.95;
+        let video_bitrate = (total_kbits / video_length - audio_bitrate).floor() as i32;
+        if video_bitrate <= 0 {
+            return Err(format!(
+                "Video is too long to fit into {} MB",
+                target_file_size_mb
+            ));
+        }
+
+        options.compression_enabled = true;
+        // Two passes land closest to the target, encoders without them cap a single pass instead.
+        let compression_options = &mut options.compression_options;
+        let family = encoder_utils::get_codec_family(&compression_options.codec);
+        compression_options.rate_control = if rate_control_utils::get_supported_modes(family)
+            .contains(&RateControlMode::TwoPassAbr)
+        {
+            RateControlMode::TwoPassAbr
+        } else {
+            RateControlMode::ConstrainedVbr
+        };
+        compression_options.bitrate = video_bitrate;
+        compression_options.max_bitrate = video_bitrate;
+        compression_options.buffer_size = None;
+        compression_options.bitrate_unit = BitrateUnit::Kbps;
+    }
+
+    Ok(options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn legacy_preset(compression_options: serde_json::Value) -> serde_json::Value {
+        serde_json::json!({
+            "name": "Legacy",
+            "settings": { "compression_options": compression_options },
+        })
+    }
+
+    #[test]
+    fn migrates_crf_flag() {
+        let cases = [
+            (Some(true), Some("crf"), None),
+            (Some(false), Some("constrained_vbr"), Some(2000)),
+            (None, None, None),
+        ];
+
+        for (using_crf, expected_mode, expected_max_bitrate) in cases {
+            let mut compression_options = serde_json::json!({ "bitrate": 2000 });
+            if let Some(using_crf) = using_crf {
+                compression_options["using_crf"] = serde_json::json!(using_crf);
+            }
+
+            let migrated = migrate_preset(legacy_preset(compression_options));
+            let migrated_options = &migrated["settings"]["compression_options"];
+
+            assert_eq!(migrated["version"], CURRENT_PRESET_VERSION);
+            assert!(migrated_options.get("using_crf").is_none());
+            assert_eq!(
+                migrated_options["rate_control"].as_str(),
+                expected_mode,
+                "using_crf: {:?}",
+                using_crf
+            );
+            assert_eq!(
+                migrated_options["max_bitrate"].as_i64(),
+                expected_max_bitrate,
+                "using_crf: {:?}",
+                using_crf
+            );
+        }
+    }
+
+    #[test]
+    fn migrates_bitrate_type() {
+        let cases = [(1, "kbps"), (2, "mbps")];
+
+        for (bitrate_type, expected_unit) in cases {
+            let migrated = migrate_preset(legacy_preset(
+                serde_json::json!({ "bitrate_type": bitrate_type }),
+            ));
+            let migrated_options = &migrated["settings"]["compression_options"];
+
+            assert!(migrated_options.get("bitrate_type").is_none());
+            assert_eq!(migrated_options["bitrate_unit"], expected_unit);
+        }
+    }
+
+    #[test]
+    fn leaves_current_presets_alone() {
+        let preset = serde_json::json!({
+            "name": "Current",
+            "version": CURRENT_PRESET_VERSION,
+            "settings": { "compression_options": { "rate_control": "two_pass_abr" } },
+        });
+
+        assert_eq!(migrate_preset(preset.clone()), preset);
+    }
+}
@@ -0,0 +1,414 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::os::windows::process::CommandExt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use uuid::Uuid;
+
+use crate::utils::analysis_utils;
+use crate::utils::ffmpeg_utils::{self, VideoEditOptions, CREATE_NO_WINDOW};
+use crate::utils::subtitle_utils::SubtitleMode;
+use crate::utils::timestamp_utils::Timestamp;
+
+const DEFAULT_SEGMENT_SECONDS: f64 = 10.0;
+// PSNR of identical frames is infinite, which JSON cannot hold.
+const MAX_PSNR: f64 = 100.0;
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QualityMetric {
+    Vmaf,
+    Ssim,
+    Psnr,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct QualityScores {
+    pub vmaf: Option<f64>,
+    pub ssim: Option<f64>,
+    pub psnr: Option<f64>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct QualitySegment {
+    pub start: Timestamp,
+    pub end: Timestamp,
+    pub start_seconds: f64,
+    pub end_seconds: f64,
+    pub scores: QualityScores,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct QualityComparisonResult {
+    pub scores: QualityScores,
+    pub segments: Vec<QualitySegment>,
+    pub vmaf_available: bool,
+    pub warnings: Vec<String>,
+}
+
+pub struct ReferenceSource {
+    pub input_args: Vec<String>,
+    pub filters: Vec<String>,
+}
+
+#[derive(Default)]
+struct FrameScores {
+    vmaf: Vec<f64>,
+    ssim: Vec<f64>,
+    psnr: Vec<f64>,
+}
+
+pub fn is_libvmaf_available() -> bool {
+    let output = Command::new(ffmpeg_utils::get_ffmpeg_command())
+        .creation_flags(CREATE_NO_WINDOW)
+        .args(["-hide_banner", "-filters"])
+        .output();
+
+    match output {
+        Ok(output) => String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .any(|line| line.split_whitespace().nth(1) == Some("libvmaf")),
+        Err(_) => false,
+    }
+}
+
+// Builds the source side of the comparison with the same cut, silence removal and geometry as
+// the export, so both sides show the same frames.
+pub fn get_reference_source(options: &VideoEditOptions) -> Result<ReferenceSource, String> {
+    let mut input_args = Vec::new();
+    let mut filters = Vec::new();
+
+    if options.silence_removal_enabled {
+        let segments = analysis_utils::get_silence_removal_segments(options)?;
+        filters.push(format!(
+            "select='{}',setpts=N/FRAME_RATE/TB",
+            ffmpeg_utils::get_keep_segments_expression(&segments)
+        ));
+    } else if options.cut_options_enabled {
+        let (start_time_seconds, _) = ffmpeg_utils::get_cut_range_in_seconds(options)?;
+        input_args.extend_from_slice(&[
+            "-ss".to_string(),
+            format!("{:.6}", start_time_seconds),
+            "-t".to_string(),
+            format!(
+                "{:.6}",
+                ffmpeg_utils::get_output_length_in_seconds(options)?
+            ),
+        ]);
+    }
+
+    input_args.extend_from_slice(&["-i".to_string(), options.input_video_path.clone()]);
+    filters.extend(ffmpeg_utils::get_geometry_filters(options));
+
+    Ok(ReferenceSource {
+        input_args,
+        filters,
+    })
+}
+
+fn get_temp_stats_path(metric: &str, extension: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "VideoCrop_{}_{}.{}",
+        metric,
+        Uuid::new_v4(),
+        extension
+    ))
+}
+
+fn get_comparison_graph(
+    reference_filters: &[String],
+    width: u32,
+    height: u32,
+    frame_rate: f64,
+    stats_paths: &[(QualityMetric, PathBuf)],
+) -> String {
+    let count = stats_paths.len();
+    let distorted_labels: String = (0..count).map(|i| format!("[d{}]", i)).collect();
+    let reference_labels: String = (0..count).map(|i| format!("[r{}]", i)).collect();
+
+    // The reference is brought to the size and rate of the output so frames line up one to one.
+    let mut reference_chain = reference_filters.to_vec();
+    reference_chain.push(format!("scale={}:{}:flags=bicubic", width, height));
+    if frame_rate > 0.0 {
+        reference_chain.push(format!("fps={:.6}", frame_rate));
+    }
+
+    let mut graph = vec![
+        format!(
+            "[0:v]settb=AVTB,setpts=PTS-STARTPTS,format=yuv420p,split={}{}",
+            count, distorted_labels
+        ),
+        format!(
+            "[1:v]{},settb=AVTB,setpts=PTS-STARTPTS,format=yuv420p,split={}{}",
+            reference_chain.join(","),
+            count,
+            reference_labels
+        ),
+    ];
+
+    for (i, (metric, path)) in stats_paths.iter().enumerate() {
+        let path = ffmpeg_utils::escape_filter_path(&path.to_string_lossy());
+        let filter = match metric {
+            QualityMetric::Vmaf => format!("libvmaf=log_path={}:log_fmt=json", path),
+            QualityMetric::Ssim => format!("ssim=stats_file={}", path),
+            QualityMetric::Psnr => format!("psnr=stats_file={}", path),
+        };
+        graph.push(format!("[d{}][r{}]{}", i, i, filter));
+    }
+
+    graph.join(";")
+}
+
+fn parse_stats_value(line: &str, key: &str) -> Option<f64> {
+    let value = line
+        .split_whitespace()
+        .find_map(|part| part.strip_prefix(key))?;
+
+    if value == "inf" {
+        Some(MAX_PSNR)
+    } else {
+        value.parse::<f64>().ok()
+    }
+}
+
+fn read_frame_scores(metric: QualityMetric, path: &Path) -> Result<Vec<f64>, String> {
+    let contents =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read quality stats: {}", e))?;
+
+    match metric {
+        QualityMetric::Ssim => Ok(contents
+            .lines()
+            .filter_map(|line| parse_stats_value(line, "All:"))
+            .collect()),
+        QualityMetric::Psnr => Ok(contents
+            .lines()
+            .filter_map(|line| parse_stats_value(line, "psnr_avg:"))
+            .map(|psnr| psnr.min(MAX_PSNR))
+            .collect()),
+        QualityMetric::Vmaf => {
+            let value: serde_json::Value = serde_json::from_str(&contents)
+                .map_err(|e| format!("Failed to parse VMAF log: {}", e))?;
+
+            Ok(value
+                .get("frames")
+                .and_then(|frames| frames.as_array())
+                .map(|frames| {
+                    frames
+                        .iter()
+                        .filter_map(|frame| frame.get("metrics")?.get("vmaf")?.as_f64())
+                        .collect()
+                })
+                .unwrap_or_default())
+        }
+    }
+}
+
+fn get_mean(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(values.iter().sum::<f64>() / values.len() as f64)
+    }
+}
+
+fn get_scores(frame_scores: &FrameScores, range: std::ops::Range<usize>) -> QualityScores {
+    let slice = |values: &Vec<f64>| {
+        let end = range.end.min(values.len());
+        get_mean(&values[range.start.min(end)..end])
+    };
+
+    QualityScores {
+        vmaf: slice(&frame_scores.vmaf),
+        ssim: slice(&frame_scores.ssim),
+        psnr: slice(&frame_scores.psnr),
+    }
+}
+
+fn run_comparison(
+    distorted_path: &str,
+    reference: &ReferenceSource,
+    metrics: &[QualityMetric],
+    length_seconds: f64,
+    progress_range: (f64, f64),
+) -> Result<(FrameScores, f64), String> {
+    let distorted_info = ffmpeg_utils::get_video_info(distorted_path)?;
+
+    let stats_paths: Vec<(QualityMetric, PathBuf)> = metrics
+        .iter()
+        .map(|metric| match metric {
+            QualityMetric::Vmaf => (*metric, get_temp_stats_path("vmaf", "json")),
+            QualityMetric::Ssim => (*metric, get_temp_stats_path("ssim", "log")),
+            QualityMetric::Psnr => (*metric, get_temp_stats_path("psnr", "log")),
+        })
+        .collect();
+
+    let mut args = vec![
+        "-y".to_string(),
+        "-i".to_string(),
+        distorted_path.to_string(),
+    ];
+    args.extend_from_slice(&reference.input_args);
+    args.extend_from_slice(&[
+        "-filter_complex".to_string(),
+        get_comparison_graph(
+            &reference.filters,
+            distorted_info.width,
+            distorted_info.height,
+            distorted_info.frame_rate,
+            &stats_paths,
+        ),
+        "-an".to_string(),
+        "-f".to_string(),
+        "null".to_string(),
+        "-".to_string(),
+    ]);
+
+    let result = ffmpeg_utils::run_ffmpeg_with_progress(&args, length_seconds, progress_range)
+        .and_then(|_| {
+            let mut frame_scores = FrameScores::default();
+            for (metric, path) in &stats_paths {
+                let scores = read_frame_scores(*metric, path)?;
+                match metric {
+                    QualityMetric::Vmaf => frame_scores.vmaf = scores,
+                    QualityMetric::Ssim => frame_scores.ssim = scores,
+                    QualityMetric::Psnr => frame_scores.psnr = scores,
+                }
+            }
+            Ok(frame_scores)
+        });
+
+    for (_, path) in &stats_paths {
+        if path.exists() {
+            if let Err(e) = fs::remove_file(path) {
+                eprintln!("Failed to remove quality stats file: {}", e);
+            }
+        }
+    }
+
+    result.map(|frame_scores| (frame_scores, distorted_info.frame_rate))
+}
+
+pub fn compare_videos(
+    distorted_path: &str,
+    reference: &ReferenceSource,
+    metrics: &[QualityMetric],
+    segment_seconds: Option<f64>,
+    length_seconds: f64,
+    progress_range: (f64, f64),
+) -> Result<QualityComparisonResult, String> {
+    let mut warnings = Vec::new();
+    let mut metrics = metrics.to_vec();
+
+    let vmaf_available = is_libvmaf_available();
+    if !vmaf_available && metrics.contains(&QualityMetric::Vmaf) {
+        metrics.retain(|metric| *metric != QualityMetric::Vmaf);
+        warnings.push("This FFmpeg build has no libvmaf, VMAF was skipped".to_string());
+    }
+
+    if metrics.is_empty() {
+        return Err("No quality metric is available".to_string());
+    }
+
+    let comparison = run_comparison(
+        distorted_path,
+        reference,
+        &metrics,
+        length_seconds,
+        progress_range,
+    );
+
+    // libvmaf can still fail when its model is missing, the other metrics are worth keeping.
+    let (frame_scores, frame_rate) = match comparison {
+        Err(err) if metrics.contains(&QualityMetric::Vmaf) && metrics.len() > 1 => {
+            warnings.push(format!("VMAF failed and was skipped: {}", err));
+            metrics.retain(|metric| *metric != QualityMetric::Vmaf);
+            run_comparison(
+                distorted_path,
+                reference,
+                &metrics,
+                length_seconds,
+                progress_range,
+            )?
+        }
+        comparison => comparison?,
+    };
+
+    let frame_count = frame_scores
+        .vmaf
+        .len()
+        .max(frame_scores.ssim.len())
+        .max(frame_scores.psnr.len());
+    let frame_rate = if frame_rate > 0.0 { frame_rate } else { 30.0 };
+    let segment_seconds = segment_seconds
+        .filter(|seconds| *seconds > 0.0)
+        .unwrap_or(DEFAULT_SEGMENT_SECONDS);
+    let frames_per_segment = ((segment_seconds * frame_rate).round() as usize).max(1);
+
+    let segments = (0..frame_count)
+        .step_by(frames_per_segment)
+        .map(|first_frame| {
+            let last_frame = (first_frame + frames_per_segment).min(frame_count);
+            let start_seconds = first_frame as f64 / frame_rate;
+            let end_seconds = last_frame as f64 / frame_rate;
+
+            QualitySegment {
+                start: Timestamp::from_seconds(start_seconds),
+                end: Timestamp::from_seconds(end_seconds),
+                start_seconds,
+                end_seconds,
+                scores: get_scores(&frame_scores, first_frame..last_frame),
+            }
+        })
+        .collect();
+
+    Ok(QualityComparisonResult {
+        scores: get_scores(&frame_scores, 0..frame_count),
+        segments,
+        vmaf_available,
+        warnings,
+    })
+}
+
+pub fn compare_quality(
+    options: &VideoEditOptions,
+    output_video_path: &str,
+    segment_seconds: Option<f64>,
+    progress_range: (f64, f64),
+) -> Result<QualityComparisonResult, String> {
+    let reference = get_reference_source(options)?;
+    let length_seconds = ffmpeg_utils::get_video_length_in_seconds(output_video_path)?;
+
+    let mut result = compare_videos(
+        output_video_path,
+        &reference,
+        &[
+            QualityMetric::Vmaf,
+            QualityMetric::Ssim,
+            QualityMetric::Psnr,
+        ],
+        segment_seconds,
+        length_seconds,
+        progress_range,
+    )?;
+
+    if options.subtitle_options.mode == SubtitleMode::BurnIn {
+        result.warnings.push(
+            "Burned in subtitles are not part of the source and lower the scores".to_string(),
+        );
+    }
+
+    Ok(result)
+}
+
+pub fn run_quality_comparison(
+    options: &VideoEditOptions,
+    output_video_path: &str,
+    segment_seconds: Option<f64>,
+) -> Result<QualityComparisonResult, String> {
+    ffmpeg_utils::start_video_progress();
+
+    let result = compare_quality(options, output_video_path, segment_seconds, (0.0, 100.0));
+    ffmpeg_utils::finish_video_progress(&result);
+
+    result
+}
@@ -0,0 +1,181 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+use crate::utils::encoder_utils::{self, CodecFamily, EncoderParam};
+use crate::utils::ffmpeg_utils::VideoCompressionOptions;
+
+#[derive(Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RateControlMode {
+    #[default]
+    Crf,
+    CappedCrf,
+    Cbr,
+    ConstrainedVbr,
+    TwoPassAbr,
+}
+
+#[derive(Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BitrateUnit {
+    #[default]
+    Kbps,
+    Mbps,
+}
+
+pub struct EncodePass<'a> {
+    pub number: u32,
+    pub log_path: &'a Path,
+}
+
+impl RateControlMode {
+    pub fn uses_crf(&self) -> bool {
+        matches!(self, RateControlMode::Crf | RateControlMode::CappedCrf)
+    }
+
+    pub fn uses_bitrate(&self) -> bool {
+        !matches!(self, RateControlMode::Crf | RateControlMode::CappedCrf)
+    }
+
+    pub fn uses_max_bitrate(&self) -> bool {
+        matches!(
+            self,
+            RateControlMode::CappedCrf | RateControlMode::ConstrainedVbr
+        )
+    }
+}
+
+impl BitrateUnit {
+    pub fn format(&self, value: i32) -> String {
+        match self {
+            BitrateUnit::Kbps => format!("{}k", value),
+            BitrateUnit::Mbps => format!("{}M", value),
+        }
+    }
+}
+
+pub fn get_supported_modes(family: CodecFamily) -> &'static [RateControlMode] {
+    match family {
+        CodecFamily::X264 | CodecFamily::X265 | CodecFamily::Other => &[
+            RateControlMode::Crf,
+            RateControlMode::CappedCrf,
+            RateControlMode::Cbr,
+            RateControlMode::ConstrainedVbr,
+            RateControlMode::TwoPassAbr,
+        ],
+        // FFmpeg's SVT-AV1 wrapper has no strict CBR and no multi-pass.
+        CodecFamily::Av1 => &[
+            RateControlMode::Crf,
+            RateControlMode::CappedCrf,
+            RateControlMode::ConstrainedVbr,
+        ],
+    }
+}
+
+// Two seconds of the peak rate leave the encoder room for scene changes, CBR keeps a one second
+// buffer so the rate stays flat.
+fn get_buffer_size(compression_options: &VideoCompressionOptions) -> i32 {
+    if let Some(buffer_size) = compression_options.buffer_size {
+        return buffer_size;
+    }
+
+    match compression_options.rate_control {
+        RateControlMode::Cbr => compression_options.bitrate,
+        _ => compression_options.max_bitrate * 2,
+    }
+}
+
+pub fn get_rate_control_args(compression_options: &VideoCompressionOptions) -> Vec<String> {
+    let unit = compression_options.bitrate_unit;
+    let bitrate = unit.format(compression_options.bitrate);
+    let max_bitrate = unit.format(compression_options.max_bitrate);
+    let buffer_size = unit.format(get_buffer_size(compression_options));
+
+    match compression_options.rate_control {
+        RateControlMode::Crf => vec!["-crf".to_string(), compression_options.crf.to_string()],
+        RateControlMode::CappedCrf => vec![
+            "-crf".to_string(),
+            compression_options.crf.to_string(),
+            "-maxrate".to_string(),
+            max_bitrate,
+            "-bufsize".to_string(),
+            buffer_size,
+        ],
+        RateControlMode::Cbr => vec![
+            "-b:v".to_string(),
+            bitrate.clone(),
+            "-minrate".to_string(),
+            bitrate.clone(),
+            "-maxrate".to_string(),
+            bitrate,
+            "-bufsize".to_string(),
+            buffer_size,
+        ],
+        RateControlMode::ConstrainedVbr => vec![
+            "-b:v".to_string(),
+            bitrate,
+            "-maxrate".to_string(),
+            max_bitrate,
+            "-bufsize".to_string(),
+            buffer_size,
+        ],
+        RateControlMode::TwoPassAbr => vec!["-b:v".to_string(), bitrate],
+    }
+}
+
+// x265 only takes its pass through x265-params, the colons of Windows paths have to be escaped
+// there.
+pub fn get_pass_args(codec: &str, pass: &EncodePass) -> (Vec<String>, Vec<EncoderParam>) {
+    let log_path = pass.log_path.to_string_lossy().to_string();
+
+    match encoder_utils::get_codec_family(codec) {
+        CodecFamily::X265 => (
+            vec![],
+            vec![
+                EncoderParam {
+                    key: "pass".to_string(),
+                    value: pass.number.to_string(),
+                },
+                EncoderParam {
+                    key: "stats".to_string(),
+                    value: log_path.replace('\\', "\\\\").replace(':', "\\:"),
+                },
+            ],
+        ),
+        _ => (
+            vec![
+                "-pass".to_string(),
+                pass.number.to_string(),
+                "-passlogfile".to_string(),
+                log_path,
+            ],
+            vec![],
+        ),
+    }
+}
+
+pub fn get_pass_log_path() -> PathBuf {
+    std::env::temp_dir().join(format!("VideoCrop_passlog_{}", Uuid::new_v4()))
+}
+
+// The encoders add their own suffixes to the log path, e.g. "-0.log" and ".mbtree" for x264.
+pub fn remove_pass_log_files(log_path: &Path) {
+    let (Some(directory), Some(log_name)) = (log_path.parent(), log_path.file_name()) else {
+        return;
+    };
+    let log_name = log_name.to_string_lossy();
+
+    let Ok(entries) = fs::read_dir(directory) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        if entry.file_name().to_string_lossy().starts_with(&*log_name) {
+            if let Err(e) = fs::remove_file(entry.path()) {
+                eprintln!("Failed to remove pass log file: {}", e);
+            }
+        }
+    }
+}
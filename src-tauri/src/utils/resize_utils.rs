@@ -0,0 +1,190 @@
+use serde::{Deserialize, Serialize};
+
+use crate::utils::ffmpeg_utils::ResizeOptions;
+
+#[derive(Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResizeMode {
+    Fit,
+    Fill,
+    #[default]
+    Stretch,
+    FitWidth,
+    FitHeight,
+}
+
+#[derive(Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScalingAlgorithm {
+    #[default]
+    Bicubic,
+    Bilinear,
+    Lanczos,
+    Spline,
+    Area,
+    Neighbor,
+}
+
+pub fn default_pad_color() -> String {
+    "black".to_string()
+}
+
+impl ScalingAlgorithm {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScalingAlgorithm::Bicubic => "bicubic",
+            ScalingAlgorithm::Bilinear => "bilinear",
+            ScalingAlgorithm::Lanczos => "lanczos",
+            ScalingAlgorithm::Spline => "spline",
+            ScalingAlgorithm::Area => "area",
+            ScalingAlgorithm::Neighbor => "neighbor",
+        }
+    }
+}
+
+pub fn round_up_to_even(value: i32) -> i32 {
+    value + value % 2
+}
+
+// Accepts FFmpeg color names and hex colors with an optional alpha.
+pub fn is_valid_pad_color(color: &str) -> bool {
+    let hex = color
+        .strip_prefix('#')
+        .or_else(|| color.strip_prefix("0x"))
+        .unwrap_or(color);
+
+    let is_hex =
+        matches!(hex.len(), 6 | 8) && hex.chars().all(|character| character.is_ascii_hexdigit());
+    let is_name = !color.is_empty()
+        && color
+            .chars()
+            .all(|character| character.is_ascii_alphabetic());
+
+    is_hex || is_name
+}
+
+// The target box is compared against the display aspect ratio (dar), so sources with non-square
+// pixels keep their shape, and every output gets square pixels. Computed sizes are truncated to
+// even numbers for 4:2:0 chroma.
+pub fn get_resize_filters(resize_options: &ResizeOptions) -> Vec<String> {
+    let width = round_up_to_even(resize_options.width);
+    let height = round_up_to_even(resize_options.height);
+    let flags = resize_options.scaler.as_str();
+
+    let scale = match resize_options.mode {
+        ResizeMode::Stretch => format!("scale={}:{}:flags={}", width, height, flags),
+        ResizeMode::Fit => format!(
+            "scale=w='if(gte(dar,{w}/{h}),{w},trunc({h}*dar/2)*2)':h='if(gte(dar,{w}/{h}),trunc({w}/dar/2)*2,{h})':flags={f}",
+            w = width,
+            h = height,
+            f = flags
+        ),
+        ResizeMode::Fill => format!(
+            "scale=w='if(gte(dar,{w}/{h}),trunc({h}*dar/2)*2,{w})':h='if(gte(dar,{w}/{h}),{h},trunc({w}/dar/2)*2)':flags={f}",
+            w = width,
+            h = height,
+            f = flags
+        ),
+        ResizeMode::FitWidth => format!(
+            "scale=w={}:h='trunc(ow/dar/2)*2':flags={}",
+            width, flags
+        ),
+        ResizeMode::FitHeight => format!(
+            "scale=w='trunc(oh*dar/2)*2':h={}:flags={}",
+            height, flags
+        ),
+    };
+
+    let mut filters = vec![scale, "setsar=1".to_string()];
+
+    match resize_options.mode {
+        ResizeMode::Fit => filters.push(format!(
+            "pad={}:{}:(ow-iw)/2:(oh-ih)/2:color={}",
+            width, height, resize_options.pad_color
+        )),
+        ResizeMode::Fill => filters.push(format!("crop={}:{}", width, height)),
+        _ => {}
+    }
+
+    filters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resize_options(mode: ResizeMode, width: i32, height: i32) -> ResizeOptions {
+        ResizeOptions {
+            width,
+            height,
+            mode,
+            scaler: ScalingAlgorithm::Lanczos,
+            pad_color: "black".to_string(),
+        }
+    }
+
+    #[test]
+    fn rounds_up_to_even() {
+        for (value, expected) in [(0, 0), (1, 2), (2, 2), (639, 640), (1080, 1080)] {
+            assert_eq!(round_up_to_even(value), expected, "{}", value);
+        }
+    }
+
+    #[test]
+    fn builds_even_resize_filters() {
+        let cases = [
+            (
+                ResizeMode::Stretch,
+                vec!["scale=642:362:flags=lanczos", "setsar=1"],
+            ),
+            (
+                ResizeMode::Fit,
+                vec![
+                    "scale=w='if(gte(dar,642/362),642,trunc(362*dar/2)*2)':h='if(gte(dar,642/362),trunc(642/dar/2)*2,362)':flags=lanczos",
+                    "setsar=1",
+                    "pad=642:362:(ow-iw)/2:(oh-ih)/2:color=black",
+                ],
+            ),
+            (
+                ResizeMode::Fill,
+                vec![
+                    "scale=w='if(gte(dar,642/362),trunc(362*dar/2)*2,642)':h='if(gte(dar,642/362),362,trunc(642/dar/2)*2)':flags=lanczos",
+                    "setsar=1",
+                    "crop=642:362",
+                ],
+            ),
+            (
+                ResizeMode::FitWidth,
+                vec!["scale=w=642:h='trunc(ow/dar/2)*2':flags=lanczos", "setsar=1"],
+            ),
+            (
+                ResizeMode::FitHeight,
+                vec!["scale=w='trunc(oh*dar/2)*2':h=362:flags=lanczos", "setsar=1"],
+            ),
+        ];
+
+        for (mode, expected) in cases {
+            assert_eq!(
+                get_resize_filters(&resize_options(mode, 641, 361)),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn validates_pad_colors() {
+        let cases = [
+            ("black", true),
+            ("#00ff00", true),
+            ("0x00ff0080", true),
+            ("ffffff", true),
+            ("#fff", false),
+            ("red;", false),
+            ("", false),
+        ];
+
+        for (color, expected) in cases {
+            assert_eq!(is_valid_pad_color(color), expected, "{}", color);
+        }
+    }
+}
@@ -0,0 +1,329 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::Instant;
+use uuid::Uuid;
+
+use crate::utils::audio_utils;
+use crate::utils::encoder_utils;
+use crate::utils::ffmpeg_utils::{self, VideoEditOptions};
+use crate::utils::frame_rate_utils;
+use crate::utils::quality_utils::{self, QualityMetric, ReferenceSource};
+use crate::utils::timestamp_utils::Timestamp;
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SampleEncodeOptions {
+    pub sample_count: u32,
+    pub sample_seconds: f64,
+    pub measure_ssim: bool,
+}
+
+impl Default for SampleEncodeOptions {
+    fn default() -> Self {
+        SampleEncodeOptions {
+            sample_count: 3,
+            sample_seconds: 5.0,
+            measure_ssim: false,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SampleEncodeResult {
+    pub start: Timestamp,
+    pub start_seconds: f64,
+    pub duration_seconds: f64,
+    pub size_bytes: u64,
+    pub encode_seconds: f64,
+    pub ssim: Option<f64>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SampleEncodePreview {
+    pub samples: Vec<SampleEncodeResult>,
+    pub output_length_seconds: f64,
+    pub projected_size_bytes: u64,
+    pub projected_size_mb: f64,
+    pub encode_speed: f64,
+    pub projected_encode_seconds: f64,
+    pub ssim: Option<f64>,
+}
+
+pub struct EncodedSample {
+    pub path: PathBuf,
+    pub start_seconds: f64,
+    pub duration_seconds: f64,
+    pub size_bytes: u64,
+    pub encode_seconds: f64,
+}
+
+impl Drop for EncodedSample {
+    fn drop(&mut self) {
+        if let Err(e) = fs::remove_file(&self.path) {
+            eprintln!("Failed to remove sample file: {}", e);
+        }
+    }
+}
+
+// Spreads the samples evenly over the cut range, each one centered in its share of the range.
+pub fn get_sample_ranges(
+    options: &VideoEditOptions,
+    sample_count: u32,
+    sample_seconds: f64,
+) -> Result<Vec<(f64, f64)>, String> {
+    let (range_start, range_end) = if options.cut_options_enabled {
+        let (start_time_seconds, _) = ffmpeg_utils::get_cut_range_in_seconds(options)?;
+        (
+            start_time_seconds,
+            start_time_seconds + ffmpeg_utils::get_output_length_in_seconds(options)?,
+        )
+    } else {
+        (
+            0.0,
+            ffmpeg_utils::get_video_length_in_seconds(&options.input_video_path)?,
+        )
+    };
+
+    let range_length = range_end - range_start;
+    if range_length <= 0.0 {
+        return Err("There is nothing to sample in the selected range".to_string());
+    }
+
+    let sample_count = sample_count.max(1);
+    if range_length <= sample_seconds * sample_count as f64 {
+        return Ok(vec![(range_start, range_length)]);
+    }
+
+    Ok((0..sample_count)
+        .map(|index| {
+            let center =
+                range_start + range_length * (2 * index + 1) as f64 / (2 * sample_count) as f64;
+            let start = (center - sample_seconds / 2.0)
+                .max(range_start)
+                .min(range_end - sample_seconds);
+            (start, sample_seconds)
+        })
+        .collect())
+}
+
+pub fn get_sample_reference(
+    options: &VideoEditOptions,
+    start_seconds: f64,
+    duration_seconds: f64,
+) -> ReferenceSource {
+    ReferenceSource {
+        input_args: vec![
+            "-ss".to_string(),
+            format!("{:.6}", start_seconds),
+            "-t".to_string(),
+            format!("{:.6}", duration_seconds),
+            "-i".to_string(),
+            options.input_video_path.clone(),
+        ],
+        filters: ffmpeg_utils::get_geometry_filters(options),
+    }
+}
+
+pub fn encode_sample(
+    options: &VideoEditOptions,
+    (start_seconds, duration_seconds): (f64, f64),
+    progress_range: (f64, f64),
+) -> Result<EncodedSample, String> {
+    let path = std::env::temp_dir().join(format!("VideoCrop_sample_{}.mp4", Uuid::new_v4()));
+
+    let mut args = vec![
+        "-y".to_string(),
+        "-ss".to_string(),
+        format!("{:.6}", start_seconds),
+        "-t".to_string(),
+        format!("{:.6}", duration_seconds),
+        "-i".to_string(),
+        options.input_video_path.clone(),
+    ];
+
+    let mut video_filters = ffmpeg_utils::get_geometry_filters(options);
+    if options.frame_rate_options.is_active() {
+        let source_frame_rate = ffmpeg_utils::get_video_info(&options.input_video_path)?.frame_rate;
+        video_filters.extend(frame_rate_utils::get_frame_rate_filter(
+            &options.frame_rate_options,
+            source_frame_rate,
+        ));
+    }
+    if !video_filters.is_empty() {
+        args.extend_from_slice(&["-vf".to_string(), video_filters.join(",")]);
+    }
+
+    args.extend(ffmpeg_utils::get_video_encoder_args(
+        &options.compression_options,
+        None,
+    ));
+
+    // Audio is encoded as well so the projected size includes it, its filters barely change
+    // the size and are left out.
+    if options.process_audio {
+        args.extend(audio_utils::get_audio_encoder_args(
+            &options.compression_options.audio_codec,
+            options.compression_options.audio_bitrate,
+            &options.audio_format_options,
+        ));
+    } else {
+        args.push("-an".to_string());
+    }
+
+    args.extend(encoder_utils::get_output_args(
+        &options.compression_options.advanced_options,
+    ));
+    args.extend_from_slice(&[
+        "-sn".to_string(),
+        "-dn".to_string(),
+        path.to_string_lossy().to_string(),
+    ]);
+
+    let encode_start = Instant::now();
+    let result = ffmpeg_utils::run_ffmpeg_with_progress(&args, duration_seconds, progress_range);
+    let encode_seconds = encode_start.elapsed().as_secs_f64();
+
+    // Dropping the sample removes whatever was written so far.
+    let mut sample = EncodedSample {
+        path,
+        start_seconds,
+        duration_seconds,
+        size_bytes: 0,
+        encode_seconds,
+    };
+    result?;
+
+    sample.size_bytes = fs::metadata(&sample.path)
+        .map_err(|e| format!("Failed to read sample size: {}", e))?
+        .len();
+
+    Ok(sample)
+}
+
+pub fn measure_sample(
+    options: &VideoEditOptions,
+    sample: &EncodedSample,
+    metric: QualityMetric,
+    progress_range: (f64, f64),
+) -> Result<Option<f64>, String> {
+    let comparison = quality_utils::compare_videos(
+        &sample.path.to_string_lossy(),
+        &get_sample_reference(options, sample.start_seconds, sample.duration_seconds),
+        &[metric],
+        None,
+        sample.duration_seconds,
+        progress_range,
+    )?;
+
+    Ok(match metric {
+        QualityMetric::Vmaf => comparison.scores.vmaf,
+        QualityMetric::Ssim => comparison.scores.ssim,
+        QualityMetric::Psnr => comparison.scores.psnr,
+    })
+}
+
+pub fn get_projected_size_bytes(samples: &[EncodedSample], output_length_seconds: f64) -> u64 {
+    let sampled_seconds: f64 = samples.iter().map(|sample| sample.duration_seconds).sum();
+    let sampled_bytes: u64 = samples.iter().map(|sample| sample.size_bytes).sum();
+
+    if sampled_seconds <= 0.0 {
+        return 0;
+    }
+
+    (sampled_bytes as f64 / sampled_seconds * output_length_seconds).round() as u64
+}
+
+fn run_sample_preview(
+    options: &VideoEditOptions,
+    sample_options: &SampleEncodeOptions,
+) -> Result<SampleEncodePreview, String> {
+    let ranges = get_sample_ranges(
+        options,
+        sample_options.sample_count,
+        sample_options.sample_seconds,
+    )?;
+    let output_length_seconds = ffmpeg_utils::get_output_length_in_seconds(options)?;
+
+    let share = 100.0 / ranges.len() as f64;
+    let encode_share = if sample_options.measure_ssim {
+        share * 0.6
+    } else {
+        share
+    };
+
+    let mut samples = Vec::new();
+    let mut results = Vec::new();
+
+    for (index, range) in ranges.into_iter().enumerate() {
+        let progress_start = share * index as f64;
+        let sample = encode_sample(
+            options,
+            range,
+            (progress_start, progress_start + encode_share),
+        )?;
+
+        let ssim = if sample_options.measure_ssim {
+            measure_sample(
+                options,
+                &sample,
+                QualityMetric::Ssim,
+                (progress_start + encode_share, progress_start + share),
+            )?
+        } else {
+            None
+        };
+
+        results.push(SampleEncodeResult {
+            start: Timestamp::from_seconds(sample.start_seconds),
+            start_seconds: sample.start_seconds,
+            duration_seconds: sample.duration_seconds,
+            size_bytes: sample.size_bytes,
+            encode_seconds: sample.encode_seconds,
+            ssim,
+        });
+        samples.push(sample);
+    }
+
+    let sampled_seconds: f64 = samples.iter().map(|sample| sample.duration_seconds).sum();
+    let encode_seconds: f64 = samples.iter().map(|sample| sample.encode_seconds).sum();
+    let encode_speed = if encode_seconds > 0.0 {
+        sampled_seconds / encode_seconds
+    } else {
+        0.0
+    };
+    let projected_size_bytes = get_projected_size_bytes(&samples, output_length_seconds);
+
+    let ssim_values: Vec<f64> = results.iter().filter_map(|result| result.ssim).collect();
+    let ssim = if ssim_values.is_empty() {
+        None
+    } else {
+        Some(ssim_values.iter().sum::<f64>() / ssim_values.len() as f64)
+    };
+
+    Ok(SampleEncodePreview {
+        samples: results,
+        output_length_seconds,
+        projected_size_bytes,
+        projected_size_mb: projected_size_bytes as f64 / 1024.0 / 1024.0,
+        encode_speed,
+        projected_encode_seconds: if encode_speed > 0.0 {
+            output_length_seconds / encode_speed
+        } else {
+            0.0
+        },
+        ssim,
+    })
+}
+
+pub fn preview_sample_encode(
+    options: &VideoEditOptions,
+    sample_options: &SampleEncodeOptions,
+) -> Result<SampleEncodePreview, String> {
+    ffmpeg_utils::start_video_progress();
+
+    let result = run_sample_preview(options, sample_options);
+    ffmpeg_utils::finish_video_progress(&result);
+
+    result
+}
@@ -0,0 +1,196 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use uuid::Uuid;
+
+use crate::utils::audio_utils::{get_number, get_string};
+use crate::utils::ffmpeg_utils;
+use crate::utils::metadata_utils::{self, Chapter, MetadataEntry};
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RemovedStreamTags {
+    pub stream_index: u32,
+    pub codec_type: String,
+    pub tags: Vec<MetadataEntry>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RemovedStream {
+    pub stream_index: u32,
+    pub codec_type: String,
+    pub codec: Option<String>,
+    pub reason: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SanitizeReport {
+    pub output_path: String,
+    pub removed_format_tags: Vec<MetadataEntry>,
+    pub removed_stream_tags: Vec<RemovedStreamTags>,
+    pub removed_chapters: Vec<Chapter>,
+    pub removed_streams: Vec<RemovedStream>,
+}
+
+fn get_tags(value: &serde_json::Value) -> Vec<MetadataEntry> {
+    value
+        .get("tags")
+        .and_then(|tags| tags.as_object())
+        .map(|tags| {
+            tags.iter()
+                .map(|(key, value)| MetadataEntry {
+                    key: key.clone(),
+                    value: value
+                        .as_str()
+                        .map(|value| value.to_string())
+                        .unwrap_or_else(|| value.to_string()),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn get_removal_reason(stream: &serde_json::Value, codec_type: &str) -> Option<String> {
+    let attached_pic = stream
+        .get("disposition")
+        .and_then(|disposition| get_number::<u32>(disposition, "attached_pic"))
+        .is_some_and(|attached_pic| attached_pic == 1);
+
+    match codec_type {
+        "video" if attached_pic => Some("Cover art".to_string()),
+        "video" | "audio" | "subtitle" => None,
+        "attachment" => Some("Attachment".to_string()),
+        "data" => Some("Data stream (e.g. telemetry or GPS)".to_string()),
+        _ => Some("Unknown stream type".to_string()),
+    }
+}
+
+fn get_sanitized_output_path(
+    input_video_path: &str,
+    output_directory: &str,
+) -> Result<String, String> {
+    let input_path = Path::new(input_video_path);
+    let extension = input_path
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_string())
+        .unwrap_or("mp4".to_string());
+
+    let new_file_name = match input_path.file_stem() {
+        Some(file_stem) => format!(
+            "{}_VideoCrop_Sanitized.{}",
+            file_stem.to_string_lossy(),
+            extension
+        ),
+        None => format!("VideoCrop_{}.{}", Uuid::new_v4(), extension),
+    };
+
+    ffmpeg_utils::resolve_output_path(&Path::new(output_directory).join(new_file_name))
+}
+
+fn build_sanitize_report(input_video_path: &str) -> Result<SanitizeReport, String> {
+    let output_str = ffmpeg_utils::run_ffprobe(&[
+        "-v",
+        "error",
+        "-show_format",
+        "-show_streams",
+        "-of",
+        "json",
+        input_video_path,
+    ])?;
+
+    let value: serde_json::Value = serde_json::from_str(&output_str)
+        .map_err(|e| format!("Failed to parse ffprobe output: {}", e))?;
+
+    let streams = value
+        .get("streams")
+        .and_then(|streams| streams.as_array())
+        .cloned()
+        .unwrap_or_default();
+
+    let mut removed_stream_tags = Vec::new();
+    let mut removed_streams = Vec::new();
+
+    for (position, stream) in streams.iter().enumerate() {
+        let stream_index = get_number(stream, "index").unwrap_or(position as u32);
+        let codec_type = get_string(stream, "codec_type").unwrap_or_default();
+
+        if let Some(reason) = get_removal_reason(stream, &codec_type) {
+            removed_streams.push(RemovedStream {
+                stream_index,
+                codec_type,
+                codec: get_string(stream, "codec_name"),
+                reason,
+            });
+            continue;
+        }
+
+        let tags = get_tags(stream);
+        if !tags.is_empty() {
+            removed_stream_tags.push(RemovedStreamTags {
+                stream_index,
+                codec_type,
+                tags,
+            });
+        }
+    }
+
+    Ok(SanitizeReport {
+        output_path: String::new(),
+        removed_format_tags: value.get("format").map(get_tags).unwrap_or_default(),
+        removed_stream_tags,
+        removed_chapters: metadata_utils::get_chapters(input_video_path)?,
+        removed_streams,
+    })
+}
+
+fn run_sanitize(input_video_path: &str, output_directory: &str) -> Result<SanitizeReport, String> {
+    let mut report = build_sanitize_report(input_video_path)?;
+    report.output_path = get_sanitized_output_path(input_video_path, output_directory)?;
+
+    let video_length = ffmpeg_utils::get_video_length_in_seconds(input_video_path)?;
+
+    // Streams are copied, only what is mapped and written by the muxer ends up in the output.
+    // Bitexact keeps the muxer from writing its version string and creation dates.
+    let args = vec![
+        "-y".to_string(),
+        "-i".to_string(),
+        input_video_path.to_string(),
+        "-map".to_string(),
+        "0:V?".to_string(),
+        "-map".to_string(),
+        "0:a?".to_string(),
+        "-map".to_string(),
+        "0:s?".to_string(),
+        "-c".to_string(),
+        "copy".to_string(),
+        "-map_metadata".to_string(),
+        "-1".to_string(),
+        "-map_metadata:s".to_string(),
+        "-1".to_string(),
+        "-map_chapters".to_string(),
+        "-1".to_string(),
+        "-fflags".to_string(),
+        "+bitexact".to_string(),
+        "-flags:v".to_string(),
+        "+bitexact".to_string(),
+        "-flags:a".to_string(),
+        "+bitexact".to_string(),
+        "-avoid_negative_ts".to_string(),
+        "make_zero".to_string(),
+        report.output_path.clone(),
+    ];
+
+    ffmpeg_utils::run_ffmpeg_with_progress(&args, video_length, (0.0, 100.0))?;
+
+    Ok(report)
+}
+
+pub fn sanitize_video(
+    input_video_path: &str,
+    output_directory: &str,
+) -> Result<SanitizeReport, String> {
+    ffmpeg_utils::start_video_progress();
+
+    let result = run_sanitize(input_video_path, output_directory);
+    ffmpeg_utils::finish_video_progress(&result);
+
+    result
+}
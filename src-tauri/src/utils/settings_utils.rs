@@ -0,0 +1,164 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+pub const SETTINGS_FILE_NAME: &str = "settings.json";
+pub const CURRENT_SETTINGS_VERSION: u64 = 1;
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverwritePolicy {
+    Rename,
+    Overwrite,
+    Skip,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub version: u64,
+    pub default_output_directory: Option<String>,
+    pub last_input_directory: Option<String>,
+    pub last_output_directory: Option<String>,
+    pub ffmpeg_path_override: Option<String>,
+    pub overwrite_policy: OverwritePolicy,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            version: CURRENT_SETTINGS_VERSION,
+            default_output_directory: None,
+            last_input_directory: None,
+            last_output_directory: None,
+            ffmpeg_path_override: None,
+            overwrite_policy: OverwritePolicy::Rename,
+        }
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref SETTINGS: Mutex<Settings> = Mutex::new(Settings::default());
+    static ref SETTINGS_PATH: Mutex<Option<PathBuf>> = Mutex::new(None);
+}
+
+fn migrate_settings(mut value: serde_json::Value) -> serde_json::Value {
+    let mut version = value.get("version").and_then(|v| v.as_u64()).unwrap_or(0);
+
+    while version < CURRENT_SETTINGS_VERSION {
+        // Unversioned files predate the overwrite policy and always renamed outputs.
+        if version == 0 && value.get("overwrite_policy").is_none() {
+            value["overwrite_policy"] = serde_json::json!("rename");
+        }
+        version += 1;
+    }
+
+    value["version"] = serde_json::json!(version);
+    value
+}
+
+fn read_settings_file(settings_path: &Path) -> Result<Settings, String> {
+    let contents = fs::read_to_string(settings_path)
+        .map_err(|e| format!("Failed to read settings file: {}", e))?;
+
+    let value: serde_json::Value = serde_json::from_str(&contents)
+        .map_err(|e| format!("Failed to parse settings file: {}", e))?;
+
+    // Migrations index into the root, which only works on an object.
+    if !value.is_object() {
+        return Err("Failed to parse settings file: root is not an object".to_string());
+    }
+
+    serde_json::from_value(migrate_settings(value))
+        .map_err(|e| format!("Failed to parse settings file: {}", e))
+}
+
+fn write_settings_file(settings_path: &Path, settings: &Settings) -> Result<(), String> {
+    if let Some(parent) = settings_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+
+    let contents = serde_json::to_string_pretty(settings)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+
+    fs::write(settings_path, contents).map_err(|e| format!("Failed to write settings file: {}", e))
+}
+
+pub fn load_settings(config_dir: &Path) {
+    let settings_path = config_dir.join(SETTINGS_FILE_NAME);
+    println!("Loading settings from: {:?}", settings_path);
+
+    let settings = if settings_path.exists() {
+        match read_settings_file(&settings_path) {
+            Ok(settings) => settings,
+            Err(err) => {
+                eprintln!("{}. Falling back to default settings.", err);
+                let backup_path = settings_path.with_extension("json.bak");
+                if let Err(e) = fs::rename(&settings_path, &backup_path) {
+                    eprintln!("Failed to back up settings file: {}", e);
+                }
+                Settings::default()
+            }
+        }
+    } else {
+        Settings::default()
+    };
+
+    if let Err(err) = write_settings_file(&settings_path, &settings) {
+        eprintln!("{}", err);
+    }
+
+    *SETTINGS.lock().unwrap() = settings;
+    *SETTINGS_PATH.lock().unwrap() = Some(settings_path);
+}
+
+pub fn get_settings() -> Settings {
+    let settings = SETTINGS.lock().unwrap();
+    settings.clone()
+}
+
+pub fn update_settings(mut settings: Settings) -> Result<Settings, String> {
+    if let Some(ffmpeg_path) = &settings.ffmpeg_path_override {
+        if ffmpeg_path.trim().is_empty() {
+            settings.ffmpeg_path_override = None;
+        } else if !Path::new(ffmpeg_path).is_dir() {
+            return Err(format!("FFmpeg directory does not exist: {}", ffmpeg_path));
+        }
+    }
+
+    settings.version = CURRENT_SETTINGS_VERSION;
+
+    save_settings(&settings)?;
+    *SETTINGS.lock().unwrap() = settings.clone();
+
+    Ok(settings)
+}
+
+fn save_settings(settings: &Settings) -> Result<(), String> {
+    match SETTINGS_PATH.lock().unwrap().as_ref() {
+        Some(settings_path) => write_settings_file(settings_path, settings),
+        None => Err("Settings have not been loaded".to_string()),
+    }
+}
+
+pub fn set_last_input_directory(file_path: &Path) {
+    let mut settings = get_settings();
+    settings.last_input_directory = file_path
+        .parent()
+        .map(|parent| parent.to_string_lossy().to_string());
+
+    if let Err(err) = update_settings(settings) {
+        eprintln!("Failed to save last input directory: {}", err);
+    }
+}
+
+pub fn set_last_output_directory(directory: &Path) {
+    let mut settings = get_settings();
+    settings.last_output_directory = Some(directory.to_string_lossy().to_string());
+
+    if let Err(err) = update_settings(settings) {
+        eprintln!("Failed to save last output directory: {}", err);
+    }
+}
@@ -0,0 +1,244 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use uuid::Uuid;
+
+use crate::utils::audio_utils::{get_number, get_string};
+use crate::utils::ffmpeg_utils::{self, VideoEditOptions};
+
+// Image based subtitles can neither be converted to text nor rendered by libass.
+const BITMAP_SUBTITLE_CODECS: [&str; 4] =
+    ["hdmv_pgs_subtitle", "dvd_subtitle", "dvb_subtitle", "xsub"];
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SubtitleStreamInfo {
+    pub stream_index: u32,
+    pub subtitle_index: u32,
+    pub codec: String,
+    pub text_based: bool,
+    pub language: Option<String>,
+    pub title: Option<String>,
+    pub default: bool,
+    pub forced: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubtitleMode {
+    None,
+    #[default]
+    Keep,
+    SoftMux,
+    BurnIn,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SubtitleOptions {
+    pub mode: SubtitleMode,
+    pub subtitle_path: String,
+    pub subtitle_index: u32,
+    pub language: Option<String>,
+}
+
+impl SubtitleOptions {
+    // Burn-in renders the external file when one is set, otherwise the selected embedded stream.
+    pub fn uses_external_file(&self) -> bool {
+        self.mode == SubtitleMode::SoftMux
+            || (self.mode == SubtitleMode::BurnIn && !self.subtitle_path.is_empty())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubtitleFormat {
+    Srt,
+    Ass,
+}
+
+impl SubtitleFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            SubtitleFormat::Srt => "srt",
+            SubtitleFormat::Ass => "ass",
+        }
+    }
+}
+
+pub fn get_subtitle_streams(video_path: &str) -> Result<Vec<SubtitleStreamInfo>, String> {
+    let output_str = ffmpeg_utils::run_ffprobe(&[
+        "-v",
+        "error",
+        "-select_streams",
+        "s",
+        "-show_entries",
+        "stream=index,codec_name:stream_tags=language,title:stream_disposition=default,forced",
+        "-of",
+        "json",
+        video_path,
+    ])?;
+
+    let value: serde_json::Value = serde_json::from_str(&output_str)
+        .map_err(|e| format!("Failed to parse ffprobe output: {}", e))?;
+
+    let streams = value
+        .get("streams")
+        .and_then(|streams| streams.as_array())
+        .cloned()
+        .unwrap_or_default();
+
+    Ok(streams
+        .iter()
+        .enumerate()
+        .map(|(subtitle_index, stream)| {
+            let tags = stream.get("tags").cloned().unwrap_or_default();
+            let disposition = stream.get("disposition").cloned().unwrap_or_default();
+            let codec = get_string(stream, "codec_name").unwrap_or_default();
+
+            SubtitleStreamInfo {
+                stream_index: get_number(stream, "index").unwrap_or(subtitle_index as u32),
+                subtitle_index: subtitle_index as u32,
+                text_based: !BITMAP_SUBTITLE_CODECS.contains(&codec.as_str()),
+                codec,
+                language: get_string(&tags, "language"),
+                title: get_string(&tags, "title"),
+                default: get_number::<u32>(&disposition, "default").is_some_and(|v| v == 1),
+                forced: get_number::<u32>(&disposition, "forced").is_some_and(|v| v == 1),
+            }
+        })
+        .collect())
+}
+
+pub fn get_burn_in_filter(options: &VideoEditOptions) -> Option<String> {
+    let subtitle_options = &options.subtitle_options;
+    if subtitle_options.mode != SubtitleMode::BurnIn {
+        return None;
+    }
+
+    if subtitle_options.uses_external_file() {
+        Some(format!(
+            "subtitles=filename={}",
+            ffmpeg_utils::escape_filter_path(&subtitle_options.subtitle_path)
+        ))
+    } else {
+        Some(format!(
+            "subtitles=filename={}:si={}",
+            ffmpeg_utils::escape_filter_path(&options.input_video_path),
+            subtitle_options.subtitle_index
+        ))
+    }
+}
+
+pub fn get_subtitle_args(
+    options: &VideoEditOptions,
+    subtitle_input_index: usize,
+) -> Result<Vec<String>, String> {
+    let subtitle_options = &options.subtitle_options;
+
+    // Burned in subtitles are part of the picture, keeping the streams as well would show them twice.
+    if matches!(
+        subtitle_options.mode,
+        SubtitleMode::None | SubtitleMode::BurnIn
+    ) {
+        return Ok(vec!["-sn".to_string()]);
+    }
+
+    // Silence removal cuts the timeline into pieces the subtitle cues cannot follow.
+    if options.silence_removal_enabled {
+        return Ok(vec!["-sn".to_string()]);
+    }
+
+    let mut args = Vec::new();
+    let mut output_index = 0;
+
+    for stream in get_subtitle_streams(&options.input_video_path)? {
+        if stream.text_based {
+            args.extend_from_slice(&["-map".to_string(), format!("0:s:{}", stream.subtitle_index)]);
+            output_index += 1;
+        }
+    }
+
+    if subtitle_options.mode == SubtitleMode::SoftMux {
+        args.extend_from_slice(&["-map".to_string(), format!("{}:s:0", subtitle_input_index)]);
+
+        if let Some(language) = &subtitle_options.language {
+            args.extend_from_slice(&[
+                format!("-metadata:s:s:{}", output_index),
+                format!("language={}", language),
+            ]);
+        }
+        output_index += 1;
+    }
+
+    if output_index == 0 {
+        return Ok(vec!["-sn".to_string()]);
+    }
+
+    // MP4 only holds text subtitles as mov_text.
+    args.extend_from_slice(&["-c:s".to_string(), "mov_text".to_string()]);
+
+    Ok(args)
+}
+
+pub fn extract_subtitles(
+    options: &VideoEditOptions,
+    subtitle_index: u32,
+    format: SubtitleFormat,
+) -> Result<String, String> {
+    let stream = get_subtitle_streams(&options.input_video_path)?
+        .into_iter()
+        .find(|stream| stream.subtitle_index == subtitle_index)
+        .ok_or(format!("Subtitle track {} does not exist", subtitle_index))?;
+
+    if !stream.text_based {
+        return Err(format!(
+            "Subtitle track {} is image based ({}) and cannot be converted to {}",
+            subtitle_index,
+            stream.codec,
+            format.extension()
+        ));
+    }
+
+    let input_path = Path::new(&options.input_video_path);
+    let output_path = Path::new(&options.output_video_path);
+
+    let new_file_name = match input_path.file_stem() {
+        Some(file_stem) => format!(
+            "{}_VideoCrop_Subtitles_{}.{}",
+            file_stem.to_string_lossy(),
+            subtitle_index + 1,
+            format.extension()
+        ),
+        None => format!("VideoCrop_{}.{}", Uuid::new_v4(), format.extension()),
+    };
+    let final_output_path = ffmpeg_utils::resolve_output_path(&output_path.join(new_file_name))?;
+
+    let mut args = vec![
+        "-hide_banner".to_string(),
+        "-y".to_string(),
+        "-i".to_string(),
+        options.input_video_path.clone(),
+    ];
+
+    // Output seeking makes the cues start relative to the cut start.
+    if options.cut_options_enabled {
+        let (start_time_seconds, end_time_seconds) =
+            ffmpeg_utils::get_cut_range_in_seconds(options)?;
+        args.extend_from_slice(&[
+            "-ss".to_string(),
+            format!("{:.6}", start_time_seconds),
+            "-to".to_string(),
+            format!("{:.6}", end_time_seconds),
+        ]);
+    }
+
+    args.extend_from_slice(&[
+        "-map".to_string(),
+        format!("0:s:{}", subtitle_index),
+        "-c:s".to_string(),
+        format.extension().to_string(),
+        final_output_path.clone(),
+    ]);
+
+    ffmpeg_utils::run_ffmpeg_for_log(&args)?;
+    Ok(final_output_path)
+}
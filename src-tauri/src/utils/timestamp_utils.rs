@@ -0,0 +1,356 @@
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+// Frame based timestamps (f:1234 and SMPTE HH:MM:SS:FF) can only be turned into seconds once
+// the frame rate of the video is known, so they are kept as typed until then.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Timestamp {
+    Seconds(f64),
+    Frame(u64),
+    Smpte {
+        hours: u64,
+        minutes: u64,
+        seconds: u64,
+        frames: u64,
+    },
+}
+
+fn parse_component(value: &str, name: &str) -> Result<u64, String> {
+    value
+        .trim()
+        .parse::<u64>()
+        .map_err(|e| format!("Failed to parse {} '{}': {}", name, value, e))
+}
+
+fn parse_seconds_component(value: &str) -> Result<f64, String> {
+    let seconds = value
+        .trim()
+        .parse::<f64>()
+        .map_err(|e| format!("Failed to parse seconds '{}': {}", value, e))?;
+
+    if !seconds.is_finite() || seconds < 0.0 {
+        return Err(format!("Invalid seconds value: {}", value));
+    }
+
+    Ok(seconds)
+}
+
+impl Timestamp {
+    pub fn parse(value: &str) -> Result<Timestamp, String> {
+        let value = value.trim();
+        if value.is_empty() {
+            return Err("Timestamp cannot be empty".to_string());
+        }
+
+        if let Some(frame) = value
+            .strip_prefix("f:")
+            .or_else(|| value.strip_prefix("F:"))
+        {
+            return Ok(Timestamp::Frame(parse_component(frame, "frame number")?));
+        }
+
+        let parts: Vec<&str> = value.split(':').collect();
+        match parts.as_slice() {
+            [seconds] => Ok(Timestamp::Seconds(parse_seconds_component(seconds)?)),
+            [minutes, seconds] => {
+                let minutes = parse_component(minutes, "minutes")?;
+                let seconds = parse_seconds_component(seconds)?;
+                if seconds >= 60.0 {
+                    return Err(format!("Seconds out of range in timestamp: {}", value));
+                }
+                Ok(Timestamp::Seconds(minutes as f64 * 60.0 + seconds))
+            }
+            [hours, minutes, seconds] => {
+                let hours = parse_component(hours, "hours")?;
+                let minutes = parse_component(minutes, "minutes")?;
+                let seconds = parse_seconds_component(seconds)?;
+                if minutes >= 60 || seconds >= 60.0 {
+                    return Err(format!(
+                        "Minutes or seconds out of range in timestamp: {}",
+                        value
+                    ));
+                }
+                Ok(Timestamp::Seconds(
+                    hours as f64 * 3600.0 + minutes as f64 * 60.0 + seconds,
+                ))
+            }
+            [hours, minutes, seconds, frames] => {
+                let hours = parse_component(hours, "hours")?;
+                let minutes = parse_component(minutes, "minutes")?;
+                let seconds = parse_component(seconds, "seconds")?;
+                let frames = parse_component(frames, "frames")?;
+                if minutes >= 60 || seconds >= 60 {
+                    return Err(format!(
+                        "Minutes or seconds out of range in timestamp: {}",
+                        value
+                    ));
+                }
+                Ok(Timestamp::Smpte {
+                    hours,
+                    minutes,
+                    seconds,
+                    frames,
+                })
+            }
+            _ => Err(format!("Invalid time format: {}", value)),
+        }
+    }
+
+    pub fn from_seconds(seconds: f64) -> Timestamp {
+        Timestamp::Seconds(seconds.max(0.0))
+    }
+
+    pub fn needs_frame_rate(&self) -> bool {
+        !matches!(self, Timestamp::Seconds(_))
+    }
+
+    pub fn to_seconds(self, frame_rate: Option<f64>) -> Result<f64, String> {
+        match self {
+            Timestamp::Seconds(seconds) => Ok(seconds),
+            Timestamp::Frame(frame) => Ok(frame as f64 / valid_frame_rate(frame_rate)?),
+            Timestamp::Smpte {
+                hours,
+                minutes,
+                seconds,
+                frames,
+            } => {
+                let frame_rate = valid_frame_rate(frame_rate)?;
+                // Non drop frame timecode counts whole nominal frames per second, so at 29.97 fps
+                // a timecode second is 30 frames and runs slightly longer than a real one.
+                let nominal_frame_rate = frame_rate.round().max(1.0);
+                if frames as f64 >= nominal_frame_rate {
+                    return Err(format!(
+                        "Frame {} is out of range for {:.3} fps",
+                        frames, frame_rate
+                    ));
+                }
+                let total_frames = (hours * 3600 + minutes * 60 + seconds) as f64
+                    * nominal_frame_rate
+                    + frames as f64;
+                Ok(total_frames / frame_rate)
+            }
+        }
+    }
+}
+
+fn valid_frame_rate(frame_rate: Option<f64>) -> Result<f64, String> {
+    match frame_rate {
+        Some(frame_rate) if frame_rate > 0.0 => Ok(frame_rate),
+        _ => Err("Frame based timestamps require a known frame rate".to_string()),
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Timestamp::Seconds(seconds) => {
+                let total_ms = (seconds * 1000.0).round() as u64;
+                let hours = total_ms / 3_600_000;
+                let minutes = (total_ms / 60_000) % 60;
+                let secs = (total_ms / 1000) % 60;
+                let ms = total_ms % 1000;
+                write!(f, "{:02}:{:02}:{:02}.{:03}", hours, minutes, secs, ms)
+            }
+            Timestamp::Frame(frame) => write!(f, "f:{}", frame),
+            Timestamp::Smpte {
+                hours,
+                minutes,
+                seconds,
+                frames,
+            } => write!(
+                f,
+                "{:02}:{:02}:{:02}:{:02}",
+                hours, minutes, seconds, frames
+            ),
+        }
+    }
+}
+
+impl Serialize for Timestamp {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+struct TimestampVisitor;
+
+impl Visitor<'_> for TimestampVisitor {
+    type Value = Timestamp;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a timestamp string or a number of seconds")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Timestamp, E> {
+        Timestamp::parse(value).map_err(E::custom)
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<Timestamp, E> {
+        if !value.is_finite() || value < 0.0 {
+            return Err(E::custom(format!("Invalid seconds value: {}", value)));
+        }
+        Ok(Timestamp::Seconds(value))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Timestamp, E> {
+        Ok(Timestamp::Seconds(value as f64))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Timestamp, E> {
+        self.visit_f64(value as f64)
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Timestamp, D::Error> {
+        deserializer.deserialize_any(TimestampVisitor)
+    }
+}
+
+pub fn parse_frame_rate(value: &str) -> Option<f64> {
+    let frame_rate = match value.trim().split_once('/') {
+        Some((numerator, denominator)) => {
+            let numerator: f64 = numerator.parse().ok()?;
+            let denominator: f64 = denominator.parse().ok()?;
+            if denominator == 0.0 {
+                return None;
+            }
+            numerator / denominator
+        }
+        None => value.trim().parse().ok()?,
+    };
+
+    if frame_rate > 0.0 && frame_rate.is_finite() {
+        Some(frame_rate)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_seconds(value: &str, expected: f64) {
+        match Timestamp::parse(value) {
+            Ok(Timestamp::Seconds(seconds)) => assert!(
+                (seconds - expected).abs() < 1e-9,
+                "{} parsed to {} instead of {}",
+                value,
+                seconds,
+                expected
+            ),
+            other => panic!("{} parsed to {:?}", value, other),
+        }
+    }
+
+    #[test]
+    fn parses_seconds() {
+        assert_seconds("42", 42.0);
+        assert_seconds("1.5", 1.5);
+        assert_seconds(" 7 ", 7.0);
+        assert!(Timestamp::parse("-1").is_err());
+        assert!(Timestamp::parse("").is_err());
+    }
+
+    #[test]
+    fn parses_minutes_and_seconds() {
+        assert_seconds("01:05", 65.0);
+        assert_seconds("2:30.25", 150.25);
+        assert!(Timestamp::parse("01:60").is_err());
+    }
+
+    #[test]
+    fn parses_hours_minutes_and_seconds() {
+        assert_seconds("01:02:03.456", 3723.456);
+        assert_seconds("00:00:00", 0.0);
+        assert!(Timestamp::parse("00:60:00").is_err());
+        assert!(Timestamp::parse("00:00:60").is_err());
+        assert!(Timestamp::parse("aa:00:00").is_err());
+    }
+
+    #[test]
+    fn parses_frame_numbers() {
+        assert_eq!(Timestamp::parse("f:120"), Ok(Timestamp::Frame(120)));
+        assert_eq!(Timestamp::parse("F:3"), Ok(Timestamp::Frame(3)));
+        assert!(Timestamp::parse("f:-1").is_err());
+
+        let timestamp = Timestamp::Frame(120);
+        assert_eq!(timestamp.to_seconds(Some(24.0)), Ok(5.0));
+        assert!(timestamp.to_seconds(None).is_err());
+        assert!(timestamp.to_seconds(Some(0.0)).is_err());
+    }
+
+    #[test]
+    fn parses_smpte() {
+        let timestamp = Timestamp::parse("00:01:02:12").unwrap();
+        assert_eq!(
+            timestamp,
+            Timestamp::Smpte {
+                hours: 0,
+                minutes: 1,
+                seconds: 2,
+                frames: 12,
+            }
+        );
+        assert!(timestamp.needs_frame_rate());
+        assert_eq!(timestamp.to_seconds(Some(24.0)), Ok(62.5));
+        assert!(timestamp.to_seconds(Some(10.0)).is_err());
+        assert!(Timestamp::parse("00:60:00:00").is_err());
+    }
+
+    #[test]
+    fn parses_smpte_at_fractional_frame_rates() {
+        let frame_rate = 30000.0 / 1001.0;
+
+        let timestamp = Timestamp::parse("01:00:00:00").unwrap();
+        let seconds = timestamp.to_seconds(Some(frame_rate)).unwrap();
+        assert!((seconds - 3603.6).abs() < 1e-6, "{}", seconds);
+
+        let timestamp = Timestamp::parse("00:00:01:15").unwrap();
+        let seconds = timestamp.to_seconds(Some(frame_rate)).unwrap();
+        assert!((seconds - 45.0 / frame_rate).abs() < 1e-9, "{}", seconds);
+
+        assert!(Timestamp::parse("00:00:00:30")
+            .unwrap()
+            .to_seconds(Some(frame_rate))
+            .is_err());
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for timestamp in [
+            Timestamp::Seconds(3723.456),
+            Timestamp::Seconds(0.0),
+            Timestamp::Frame(42),
+            Timestamp::Smpte {
+                hours: 1,
+                minutes: 2,
+                seconds: 3,
+                frames: 4,
+            },
+        ] {
+            let text = timestamp.to_string();
+            let parsed = Timestamp::parse(&text).unwrap();
+            match (timestamp, parsed) {
+                (Timestamp::Seconds(expected), Timestamp::Seconds(seconds)) => {
+                    assert!((seconds - expected).abs() < 1e-9, "{} round tripped", text)
+                }
+                _ => assert_eq!(parsed, timestamp),
+            }
+        }
+
+        assert_eq!(Timestamp::Seconds(3723.456).to_string(), "01:02:03.456");
+    }
+
+    #[test]
+    fn parses_frame_rates() {
+        assert_eq!(parse_frame_rate("30/1"), Some(30.0));
+        assert_eq!(parse_frame_rate("25"), Some(25.0));
+        assert!((parse_frame_rate("30000/1001").unwrap() - 29.97).abs() < 0.001);
+        assert_eq!(parse_frame_rate("0/0"), None);
+        assert_eq!(parse_frame_rate("0/1"), None);
+        assert_eq!(parse_frame_rate("abc"), None);
+    }
+}
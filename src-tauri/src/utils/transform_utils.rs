@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+
+use crate::utils::resize_utils;
+
+#[derive(Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransformRotation {
+    #[default]
+    None,
+    Clockwise90,
+    Rotate180,
+    Clockwise270,
+    Angle,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TransformOptions {
+    pub rotation: TransformRotation,
+    // Clockwise, only used by the free angle rotation.
+    pub angle_degrees: f64,
+    pub fill_color: String,
+    pub horizontal_flip: bool,
+    pub vertical_flip: bool,
+}
+
+impl Default for TransformOptions {
+    fn default() -> Self {
+        TransformOptions {
+            rotation: TransformRotation::None,
+            angle_degrees: 0.0,
+            fill_color: resize_utils::default_pad_color(),
+            horizontal_flip: false,
+            vertical_flip: false,
+        }
+    }
+}
+
+impl TransformOptions {
+    pub fn is_active(&self) -> bool {
+        self.rotation != TransformRotation::None || self.horizontal_flip || self.vertical_flip
+    }
+}
+
+// The free angle rotation grows the frame to the bounding box of the rotated video, so no
+// corner is cut off, and truncates it to even numbers for 4:2:0 chroma.
+pub fn get_transformed_size(options: &TransformOptions, width: u32, height: u32) -> (u32, u32) {
+    match options.rotation {
+        TransformRotation::Clockwise90 | TransformRotation::Clockwise270 => (height, width),
+        TransformRotation::Angle => {
+            let radians = options.angle_degrees.to_radians();
+            let (sin, cos) = (radians.sin().abs(), radians.cos().abs());
+            let rotated_width = width as f64 * cos + height as f64 * sin;
+            let rotated_height = width as f64 * sin + height as f64 * cos;
+
+            (
+                (rotated_width / 2.0).trunc() as u32 * 2,
+                (rotated_height / 2.0).trunc() as u32 * 2,
+            )
+        }
+        TransformRotation::None | TransformRotation::Rotate180 => (width, height),
+    }
+}
+
+// Flips are applied after the rotation, both act on the frame the crop is picked on.
+pub fn get_transform_filters(options: &TransformOptions) -> Vec<String> {
+    let mut filters = Vec::new();
+
+    match options.rotation {
+        TransformRotation::None => {}
+        TransformRotation::Clockwise90 => filters.push("transpose=clock".to_string()),
+        TransformRotation::Rotate180 => {
+            filters.extend_from_slice(&["hflip".to_string(), "vflip".to_string()])
+        }
+        TransformRotation::Clockwise270 => filters.push("transpose=cclock".to_string()),
+        TransformRotation::Angle => {
+            let radians = options.angle_degrees.to_radians();
+            filters.push(format!(
+                "rotate={a:.6}:ow='trunc(rotw({a:.6})/2)*2':oh='trunc(roth({a:.6})/2)*2':fillcolor={c}",
+                a = radians,
+                c = options.fill_color
+            ));
+        }
+    }
+
+    if options.horizontal_flip {
+        filters.push("hflip".to_string());
+    }
+
+    if options.vertical_flip {
+        filters.push("vflip".to_string());
+    }
+
+    filters
+}
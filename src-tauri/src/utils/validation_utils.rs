@@ -0,0 +1,1336 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use uuid::Uuid;
+
+use crate::utils::animation_utils::{AnimationExportOptions, AnimationFormat};
+use crate::utils::audio_utils::{self, AudioTrackMode, ExternalAudioMode};
+use crate::utils::crf_search_utils::{self, CrfSearchOptions};
+use crate::utils::encoder_utils::{self, CodecFamily};
+use crate::utils::ffmpeg_utils::{self, VideoEditOptions, VideoInfo};
+use crate::utils::frame_rate_utils::{self, FrameRateMode, FrameRateOptions};
+use crate::utils::metadata_utils::RotationMode;
+use crate::utils::quality_utils::{self, QualityMetric};
+use crate::utils::rate_control_utils::{self, RateControlMode};
+use crate::utils::resize_utils::{self, ResizeMode};
+use crate::utils::sample_utils::SampleEncodeOptions;
+use crate::utils::subtitle_utils::{self, SubtitleMode};
+use crate::utils::transform_utils::{self, TransformRotation};
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidationSeverity {
+    Error,
+    Warning,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ValidationIssue {
+    pub field: String,
+    pub severity: ValidationSeverity,
+    pub message: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ValidationReport {
+    pub valid: bool,
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    fn error(&mut self, field: &str, message: String) {
+        self.valid = false;
+        self.issues.push(ValidationIssue {
+            field: field.to_string(),
+            severity: ValidationSeverity::Error,
+            message,
+        });
+    }
+
+    fn warning(&mut self, field: &str, message: String) {
+        self.issues.push(ValidationIssue {
+            field: field.to_string(),
+            severity: ValidationSeverity::Warning,
+            message,
+        });
+    }
+
+    pub fn error_summary(&self) -> String {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == ValidationSeverity::Error)
+            .map(|issue| format!("{}: {}", issue.field, issue.message))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    pub fn into_result(self) -> Result<(), String> {
+        if self.valid {
+            Ok(())
+        } else {
+            Err(self.error_summary())
+        }
+    }
+}
+
+pub fn get_crf_range(codec: &str) -> Option<(i32, i32)> {
+    match codec {
+        "libx264" | "libx265" => Some((0, 51)),
+        "libsvtav1" | "libaom-av1" => Some((0, 63)),
+        _ => None,
+    }
+}
+
+pub fn validate_edit_options(options: &VideoEditOptions) -> ValidationReport {
+    let mut report = ValidationReport {
+        valid: true,
+        issues: Vec::new(),
+    };
+
+    validate_output_directory(&options.output_video_path, &mut report);
+
+    if !Path::new(&options.input_video_path).is_file() {
+        report.error(
+            "input_video_path",
+            format!("Input file does not exist: {}", options.input_video_path),
+        );
+        return report;
+    }
+
+    let video_info = match ffmpeg_utils::get_video_info(&options.input_video_path) {
+        Ok(info) => info,
+        Err(err) => {
+            report.error(
+                "input_video_path",
+                format!("Failed to probe video: {}", err),
+            );
+            return report;
+        }
+    };
+
+    let video_length = ffmpeg_utils::get_video_length_in_seconds(&options.input_video_path).ok();
+
+    if options.cut_options_enabled {
+        validate_cut_options(options, video_length, &mut report);
+    }
+
+    let (frame_width, frame_height) = transform_utils::get_transformed_size(
+        &options.transform_options,
+        video_info.width,
+        video_info.height,
+    );
+
+    if options.transform_options.is_active() {
+        validate_transform_options(options, video_info.rotation, &mut report);
+    }
+
+    if options.crop_enabled {
+        validate_crop_options(options, frame_width, frame_height, &mut report);
+    }
+
+    if options.resize_enabled {
+        validate_resize_options(options, &mut report);
+    }
+
+    if options.compression_enabled {
+        validate_compression_options(options, &mut report);
+    }
+
+    if options.silence_removal_enabled {
+        validate_silence_removal_options(options, &mut report);
+    }
+
+    if options.audio_track_options.mode != AudioTrackMode::Default {
+        validate_audio_track_options(options, &mut report);
+    }
+
+    if options.audio_level_options.is_active() {
+        validate_audio_level_options(options, video_length, &mut report);
+    }
+
+    validate_audio_format_options(options, &mut report);
+
+    if options.external_audio_enabled {
+        validate_external_audio_options(options, &mut report);
+    }
+
+    if options.subtitle_options.mode != SubtitleMode::None {
+        validate_subtitle_options(options, &mut report);
+    }
+
+    validate_metadata_options(options, video_info.rotation, &mut report);
+
+    validate_frame_rate_options(options, &video_info, &mut report);
+
+    report
+}
+
+pub fn validate_animation_export(
+    options: &VideoEditOptions,
+    animation_options: &AnimationExportOptions,
+) -> ValidationReport {
+    let mut animation_edit_options = options.clone();
+    animation_edit_options.compression_enabled = false;
+    animation_edit_options.silence_removal_enabled = false;
+    animation_edit_options.external_audio_enabled = false;
+    animation_edit_options.frame_rate_options = FrameRateOptions::default();
+    animation_edit_options.subtitle_options.mode = SubtitleMode::None;
+    let mut report = validate_edit_options(&animation_edit_options);
+    if !report.valid {
+        return report;
+    }
+
+    if animation_options.fps <= 0.0 {
+        report.error(
+            "animation_options.fps",
+            "Frame rate must be positive".to_string(),
+        );
+    } else if animation_options.format == AnimationFormat::Gif && animation_options.fps > 50.0 {
+        report.warning(
+            "animation_options.fps",
+            "GIF delays are stored in hundredths of a second, most viewers cap playback at 50 fps"
+                .to_string(),
+        );
+    }
+
+    if animation_options.max_width == Some(0) || animation_options.max_height == Some(0) {
+        report.error(
+            "animation_options.max_width",
+            "Maximum dimensions must be positive".to_string(),
+        );
+    }
+
+    if let Ok(video_length) = ffmpeg_utils::get_output_length_in_seconds(options) {
+        if video_length > 60.0 {
+            report.warning(
+                "cut_options",
+                format!(
+                    "Exporting {:.0}s as an animation will produce a very large file",
+                    video_length
+                ),
+            );
+        }
+    }
+
+    report
+}
+
+pub fn validate_sample_encode(
+    options: &VideoEditOptions,
+    sample_options: &SampleEncodeOptions,
+) -> ValidationReport {
+    let mut report = validate_edit_options(options);
+    if !report.valid {
+        return report;
+    }
+
+    if !options.compression_enabled {
+        report.error(
+            "compression_enabled",
+            "Compression has to be enabled to preview its settings".to_string(),
+        );
+    }
+
+    if !(1..=10).contains(&sample_options.sample_count) {
+        report.error(
+            "sample_options.sample_count",
+            "Sample count must be between 1 and 10".to_string(),
+        );
+    }
+
+    if !(1.0..=60.0).contains(&sample_options.sample_seconds) {
+        report.error(
+            "sample_options.sample_seconds",
+            "Sample length must be between 1 and 60 seconds".to_string(),
+        );
+    }
+
+    report
+}
+
+pub fn validate_crf_search(
+    options: &VideoEditOptions,
+    search_options: &CrfSearchOptions,
+) -> ValidationReport {
+    let mut search_edit_options = options.clone();
+    search_edit_options.compression_options.rate_control = RateControlMode::Crf;
+    let mut report = validate_sample_encode(
+        &search_edit_options,
+        &SampleEncodeOptions {
+            sample_count: search_options.sample_count,
+            sample_seconds: search_options.sample_seconds,
+            measure_ssim: false,
+        },
+    );
+    if !report.valid {
+        return report;
+    }
+
+    match crf_search_utils::get_search_range(&options.compression_options.codec, search_options) {
+        Ok((min_crf, max_crf)) if min_crf > max_crf => report.error(
+            "search_options.min_crf",
+            "The minimum CRF must not be above the maximum CRF".to_string(),
+        ),
+        Ok(_) => {}
+        Err(err) => report.error("compression_options.codec", err),
+    }
+
+    let target_range = match search_options.metric {
+        QualityMetric::Vmaf => 0.0..=100.0,
+        QualityMetric::Ssim => 0.0..=1.0,
+        QualityMetric::Psnr => 0.0..=100.0,
+    };
+    if !target_range.contains(&search_options.target_score) {
+        report.error(
+            "search_options.target_score",
+            format!(
+                "Target score must be between {} and {}",
+                target_range.start(),
+                target_range.end()
+            ),
+        );
+    }
+
+    if search_options.metric == QualityMetric::Vmaf && !quality_utils::is_libvmaf_available() {
+        report.error(
+            "search_options.metric",
+            "This FFmpeg build has no libvmaf, search for an SSIM target instead".to_string(),
+        );
+    }
+
+    report
+}
+
+pub fn validate_sanitize(input_video_path: &str, output_video_path: &str) -> ValidationReport {
+    let mut report = ValidationReport {
+        valid: true,
+        issues: Vec::new(),
+    };
+
+    validate_output_directory(output_video_path, &mut report);
+
+    if !Path::new(input_video_path).is_file() {
+        report.error(
+            "input_video_path",
+            format!("Input file does not exist: {}", input_video_path),
+        );
+    }
+
+    report
+}
+
+fn validate_output_directory(output_video_path: &str, report: &mut ValidationReport) {
+    let output_dir = Path::new(output_video_path);
+    if output_video_path.is_empty() || !output_dir.is_dir() {
+        report.error(
+            "output_video_path",
+            format!("Output directory does not exist: {}", output_video_path),
+        );
+        return;
+    }
+
+    let probe_file = output_dir.join(format!(".VideoCrop_write_check_{}", Uuid::new_v4()));
+    match fs::write(&probe_file, []) {
+        Ok(()) => {
+            let _ = fs::remove_file(&probe_file);
+        }
+        Err(e) => report.error(
+            "output_video_path",
+            format!("Output directory is not writable: {}", e),
+        ),
+    }
+}
+
+fn validate_cut_options(
+    options: &VideoEditOptions,
+    video_length: Option<f64>,
+    report: &mut ValidationReport,
+) {
+    let (start_time, end_time) = match ffmpeg_utils::get_cut_range_in_seconds(options) {
+        Ok(range) => range,
+        Err(err) => {
+            report.error("cut_options", err);
+            return;
+        }
+    };
+
+    if end_time <= start_time {
+        report.error(
+            "cut_options.end_time_string",
+            "End time must be after the start time".to_string(),
+        );
+    }
+
+    if let Some(video_length) = video_length {
+        if end_time > video_length {
+            report.warning(
+                "cut_options.end_time_string",
+                format!(
+                    "End time is past the end of the video ({:.3}s) and will be clamped",
+                    video_length
+                ),
+            );
+        }
+    }
+}
+
+fn validate_crop_options(
+    options: &VideoEditOptions,
+    frame_width: u32,
+    frame_height: u32,
+    report: &mut ValidationReport,
+) {
+    let crop_options = &options.crop_options;
+
+    // Odd sizes are cut down by a pixel for 4:2:0 chroma, so a single pixel would leave nothing.
+    if crop_options.width < 2 {
+        report.error(
+            "crop_options.width",
+            "Crop width must be at least 2 pixels".to_string(),
+        );
+    } else if crop_options.width % 2 != 0 {
+        report.warning(
+            "crop_options.width",
+            format!(
+                "Crop width is odd and will be cut down to {} pixels",
+                crop_options.width - 1
+            ),
+        );
+    }
+
+    if crop_options.height < 2 {
+        report.error(
+            "crop_options.height",
+            "Crop height must be at least 2 pixels".to_string(),
+        );
+    } else if crop_options.height % 2 != 0 {
+        report.warning(
+            "crop_options.height",
+            format!(
+                "Crop height is odd and will be cut down to {} pixels",
+                crop_options.height - 1
+            ),
+        );
+    }
+
+    if crop_options.starting_x_offset < 0 {
+        report.error(
+            "crop_options.starting_x_offset",
+            "Crop X offset cannot be negative".to_string(),
+        );
+    }
+
+    if crop_options.starting_y_offset < 0 {
+        report.error(
+            "crop_options.starting_y_offset",
+            "Crop Y offset cannot be negative".to_string(),
+        );
+    }
+
+    if crop_options.starting_x_offset as i64 + crop_options.width as i64 > frame_width as i64 {
+        report.error(
+            "crop_options.width",
+            format!("Crop area exceeds the frame width of {}", frame_width),
+        );
+    }
+
+    if crop_options.starting_y_offset as i64 + crop_options.height as i64 > frame_height as i64 {
+        report.error(
+            "crop_options.height",
+            format!("Crop area exceeds the frame height of {}", frame_height),
+        );
+    }
+}
+
+fn validate_transform_options(
+    options: &VideoEditOptions,
+    rotation: u32,
+    report: &mut ValidationReport,
+) {
+    let transform_options = &options.transform_options;
+
+    if transform_options.rotation == TransformRotation::Angle {
+        if !(-360.0..=360.0).contains(&transform_options.angle_degrees) {
+            report.error(
+                "transform_options.angle_degrees",
+                "Rotation angle must be between -360 and 360 degrees".to_string(),
+            );
+        }
+
+        if !resize_utils::is_valid_pad_color(&transform_options.fill_color) {
+            report.error(
+                "transform_options.fill_color",
+                format!("Invalid fill color: {}", transform_options.fill_color),
+            );
+        }
+    }
+
+    // The kept rotation tag would turn the transformed frame a second time on playback.
+    if options.metadata_options.rotation_mode == RotationMode::Keep && rotation != 0 {
+        report.error(
+            "transform_options",
+            "Rotating or flipping needs the video rotation applied to the frames".to_string(),
+        );
+    }
+}
+
+fn validate_resize_options(options: &VideoEditOptions, report: &mut ValidationReport) {
+    let resize_options = &options.resize_options;
+
+    if options.crop_enabled {
+        report.warning(
+            "resize_options",
+            "Resizing is ignored while cropping is enabled".to_string(),
+        );
+        return;
+    }
+
+    // The other dimension follows the aspect ratio in the single dimension modes.
+    let uses_width = resize_options.mode != ResizeMode::FitHeight;
+    let uses_height = resize_options.mode != ResizeMode::FitWidth;
+
+    if uses_width && resize_options.width <= 0 {
+        report.error(
+            "resize_options.width",
+            "Resize width must be positive".to_string(),
+        );
+    } else if uses_width && resize_options.width % 2 != 0 {
+        report.warning(
+            "resize_options.width",
+            format!(
+                "Resize width will be rounded up to {}",
+                resize_utils::round_up_to_even(resize_options.width)
+            ),
+        );
+    }
+
+    if uses_height && resize_options.height <= 0 {
+        report.error(
+            "resize_options.height",
+            "Resize height must be positive".to_string(),
+        );
+    } else if uses_height && resize_options.height % 2 != 0 {
+        report.warning(
+            "resize_options.height",
+            format!(
+                "Resize height will be rounded up to {}",
+                resize_utils::round_up_to_even(resize_options.height)
+            ),
+        );
+    }
+
+    if resize_options.mode == ResizeMode::Fit
+        && !resize_utils::is_valid_pad_color(&resize_options.pad_color)
+    {
+        report.error(
+            "resize_options.pad_color",
+            format!("Invalid pad color: {}", resize_options.pad_color),
+        );
+    }
+}
+
+fn validate_compression_options(options: &VideoEditOptions, report: &mut ValidationReport) {
+    let compression_options = &options.compression_options;
+    let rate_control = compression_options.rate_control;
+
+    if !rate_control_utils::get_supported_modes(encoder_utils::get_codec_family(
+        &compression_options.codec,
+    ))
+    .contains(&rate_control)
+    {
+        report.error(
+            "compression_options.rate_control",
+            format!(
+                "{} does not support this rate control mode",
+                compression_options.codec
+            ),
+        );
+    }
+
+    if rate_control.uses_crf() {
+        match get_crf_range(&compression_options.codec) {
+            Some((min_crf, max_crf)) => {
+                if compression_options.crf < min_crf || compression_options.crf > max_crf {
+                    report.error(
+                        "compression_options.crf",
+                        format!(
+                            "CRF for {} must be between {} and {}",
+                            compression_options.codec, min_crf, max_crf
+                        ),
+                    );
+                }
+            }
+            None => report.warning(
+                "compression_options.codec",
+                format!(
+                    "Unknown codec {}, CRF range cannot be checked",
+                    compression_options.codec
+                ),
+            ),
+        }
+    }
+
+    if rate_control.uses_bitrate() && compression_options.bitrate <= 0 {
+        report.error(
+            "compression_options.bitrate",
+            "Bitrate must be positive".to_string(),
+        );
+    }
+
+    if rate_control.uses_max_bitrate() {
+        if compression_options.max_bitrate <= 0 {
+            report.error(
+                "compression_options.max_bitrate",
+                "Maximum bitrate must be positive".to_string(),
+            );
+        } else if rate_control == RateControlMode::ConstrainedVbr
+            && compression_options.max_bitrate < compression_options.bitrate
+        {
+            report.error(
+                "compression_options.max_bitrate",
+                "Maximum bitrate must not be below the target bitrate".to_string(),
+            );
+        }
+    }
+
+    if compression_options
+        .buffer_size
+        .is_some_and(|buffer_size| buffer_size <= 0)
+    {
+        report.error(
+            "compression_options.buffer_size",
+            "Buffer size must be positive".to_string(),
+        );
+    }
+
+    if compression_options.audio_bitrate < 0 {
+        report.error(
+            "compression_options.audio_bitrate",
+            "Audio bitrate cannot be negative".to_string(),
+        );
+    }
+
+    validate_advanced_encoder_options(options, report);
+}
+
+fn validate_advanced_encoder_options(options: &VideoEditOptions, report: &mut ValidationReport) {
+    let codec = &options.compression_options.codec;
+    let advanced_options = &options.compression_options.advanced_options;
+    let family = encoder_utils::get_codec_family(codec);
+
+    if let Some(pixel_format) = advanced_options.pixel_format.as_str() {
+        if family != CodecFamily::Other
+            && !encoder_utils::get_supported_pixel_formats(family)
+                .contains(&advanced_options.pixel_format)
+        {
+            report.error(
+                "compression_options.advanced_options.pixel_format",
+                format!("{} cannot encode {}", codec, pixel_format),
+            );
+        }
+    }
+
+    if let Some(tune) = &advanced_options.tune {
+        if !encoder_utils::get_supported_tunes(family).contains(&tune.as_str()) {
+            report.error(
+                "compression_options.advanced_options.tune",
+                format!("Unsupported tune for {}: {}", codec, tune),
+            );
+        }
+    }
+
+    if let Some(profile) = &advanced_options.profile {
+        if !encoder_utils::get_supported_profiles(family).contains(&profile.as_str()) {
+            report.error(
+                "compression_options.advanced_options.profile",
+                format!("Unsupported profile for {}: {}", codec, profile),
+            );
+        } else if let Some(pixel_format) = advanced_options.pixel_format.as_str() {
+            if !encoder_utils::get_profile_pixel_formats(family, profile)
+                .contains(&advanced_options.pixel_format)
+            {
+                report.error(
+                    "compression_options.advanced_options.profile",
+                    format!("Profile {} does not support {}", profile, pixel_format),
+                );
+            }
+        }
+    }
+
+    if advanced_options.keyframe_interval == Some(0) {
+        report.error(
+            "compression_options.advanced_options.keyframe_interval",
+            "Keyframe interval must be at least 1 frame".to_string(),
+        );
+    }
+
+    if !advanced_options.codec_params.is_empty() && !encoder_utils::supports_codec_params(codec) {
+        report.error(
+            "compression_options.advanced_options.codec_params",
+            format!("{} does not take encoder parameters", codec),
+        );
+    }
+
+    for (index, param) in advanced_options.codec_params.iter().enumerate() {
+        if !encoder_utils::is_valid_codec_param(param) {
+            report.error(
+                &format!(
+                    "compression_options.advanced_options.codec_params[{}]",
+                    index
+                ),
+                format!("Invalid encoder parameter: '{}={}'", param.key, param.value),
+            );
+        }
+    }
+
+    validate_extra_args(&advanced_options.extra_args, report);
+}
+
+fn validate_extra_args(extra_args: &[String], report: &mut ValidationReport) {
+    if extra_args.is_empty() {
+        return;
+    }
+
+    // Two values in a row would make FFmpeg read the second one as another output file.
+    let mut previous_was_option = false;
+    for (index, arg) in extra_args.iter().enumerate() {
+        let field = format!("compression_options.advanced_options.extra_args[{}]", index);
+
+        if encoder_utils::is_extra_arg_option(arg) {
+            if encoder_utils::is_reserved_extra_arg(arg) {
+                report.error(
+                    &field,
+                    format!("{} is set by VideoCrop and cannot be passed again", arg),
+                );
+            }
+            previous_was_option = true;
+        } else if previous_was_option {
+            previous_was_option = false;
+        } else {
+            report.error(&field, format!("'{}' does not belong to an option", arg));
+        }
+    }
+
+    report.warning(
+        "compression_options.advanced_options.extra_args",
+        "Extra arguments are passed to FFmpeg unchecked".to_string(),
+    );
+}
+
+fn validate_silence_removal_options(options: &VideoEditOptions, report: &mut ValidationReport) {
+    let removal_options = &options.silence_removal_options;
+
+    if removal_options.noise_threshold_db >= 0.0 {
+        report.error(
+            "silence_removal_options.noise_threshold_db",
+            "Noise threshold must be below 0 dB".to_string(),
+        );
+    }
+
+    if removal_options.min_silence_seconds <= 0.0 {
+        report.error(
+            "silence_removal_options.min_silence_seconds",
+            "Minimum silence duration must be positive".to_string(),
+        );
+    }
+
+    if removal_options.padding_seconds < 0.0 {
+        report.error(
+            "silence_removal_options.padding_seconds",
+            "Padding cannot be negative".to_string(),
+        );
+    } else if removal_options.padding_seconds * 2.0 >= removal_options.min_silence_seconds {
+        report.warning(
+            "silence_removal_options.padding_seconds",
+            "Padding is at least half the minimum silence, so short silences will be kept"
+                .to_string(),
+        );
+    }
+
+    if !options.process_audio {
+        report.warning(
+            "silence_removal_enabled",
+            "Silence is detected from the audio track even though audio is not exported"
+                .to_string(),
+        );
+    }
+}
+
+fn validate_audio_track_options(options: &VideoEditOptions, report: &mut ValidationReport) {
+    let selected_tracks = &options.audio_track_options.selected_tracks;
+
+    if selected_tracks.is_empty() {
+        report.error(
+            "audio_track_options.selected_tracks",
+            "At least one audio track must be selected".to_string(),
+        );
+        return;
+    }
+
+    let audio_streams = match audio_utils::get_audio_streams(&options.input_video_path) {
+        Ok(streams) => streams,
+        Err(err) => {
+            report.error(
+                "audio_track_options",
+                format!("Failed to probe audio tracks: {}", err),
+            );
+            return;
+        }
+    };
+
+    for track in selected_tracks {
+        if *track as usize >= audio_streams.len() {
+            report.error(
+                "audio_track_options.selected_tracks",
+                format!(
+                    "Audio track {} does not exist, the video has {} audio tracks",
+                    track + 1,
+                    audio_streams.len()
+                ),
+            );
+        }
+    }
+}
+
+fn validate_audio_level_options(
+    options: &VideoEditOptions,
+    video_length: Option<f64>,
+    report: &mut ValidationReport,
+) {
+    let level_options = &options.audio_level_options;
+
+    if level_options.normalize_enabled {
+        if !(-70.0..=-5.0).contains(&level_options.target_lufs) {
+            report.error(
+                "audio_level_options.target_lufs",
+                "Target loudness must be between -70 and -5 LUFS".to_string(),
+            );
+        }
+
+        if !(-9.0..=0.0).contains(&level_options.true_peak_db) {
+            report.error(
+                "audio_level_options.true_peak_db",
+                "True peak must be between -9 and 0 dBTP".to_string(),
+            );
+        }
+
+        if !(1.0..=50.0).contains(&level_options.loudness_range) {
+            report.error(
+                "audio_level_options.loudness_range",
+                "Loudness range must be between 1 and 50 LU".to_string(),
+            );
+        }
+
+        if options.audio_track_options.mode == AudioTrackMode::Keep
+            && options.audio_track_options.selected_tracks.len() > 1
+        {
+            report.warning(
+                "audio_level_options.normalize_enabled",
+                "Only the first kept audio track is measured, the same correction is applied to all tracks"
+                    .to_string(),
+            );
+        }
+
+        if level_options.gain_db != 0.0 {
+            report.warning(
+                "audio_level_options.gain_db",
+                "Gain is applied after normalization and moves the output off the target loudness"
+                    .to_string(),
+            );
+        }
+    }
+
+    if level_options.fade_in_seconds < 0.0 || level_options.fade_out_seconds < 0.0 {
+        report.error(
+            "audio_level_options",
+            "Fade durations cannot be negative".to_string(),
+        );
+    }
+
+    let output_length = ffmpeg_utils::get_output_length_in_seconds(options)
+        .ok()
+        .or(video_length);
+    if let Some(output_length) = output_length {
+        if level_options.fade_in_seconds + level_options.fade_out_seconds > output_length {
+            report.warning(
+                "audio_level_options",
+                "Fades are longer than the output and will overlap".to_string(),
+            );
+        }
+    }
+}
+
+fn validate_audio_format_options(options: &VideoEditOptions, report: &mut ValidationReport) {
+    let format_options = &options.audio_format_options;
+
+    if let Some(sample_rate) = format_options.sample_rate {
+        if !(8000..=192000).contains(&sample_rate) {
+            report.error(
+                "audio_format_options.sample_rate",
+                "Sample rate must be between 8000 and 192000 Hz".to_string(),
+            );
+        }
+    }
+
+    if let Some(channel_layout) = &format_options.channel_layout {
+        if !["mono", "stereo", "2.1", "quad", "5.0", "5.1", "7.1"]
+            .contains(&channel_layout.as_str())
+        {
+            report.error(
+                "audio_format_options.channel_layout",
+                format!("Unsupported channel layout: {}", channel_layout),
+            );
+        }
+    }
+
+    if ![16, 24, 32].contains(&format_options.pcm_bit_depth) {
+        report.error(
+            "audio_format_options.pcm_bit_depth",
+            "WAV bit depth must be 16, 24 or 32".to_string(),
+        );
+    }
+
+    if options.compression_enabled
+        && options.process_audio
+        && options.compression_options.audio_codec == "pcm"
+    {
+        report.error(
+            "compression_options.audio_codec",
+            "WAV audio can only be used for audio extraction".to_string(),
+        );
+    }
+}
+
+fn validate_external_audio_options(options: &VideoEditOptions, report: &mut ValidationReport) {
+    let external_options = &options.external_audio_options;
+
+    if !Path::new(&external_options.audio_path).is_file() {
+        report.error(
+            "external_audio_options.audio_path",
+            format!("Audio file does not exist: {}", external_options.audio_path),
+        );
+        return;
+    }
+
+    match audio_utils::get_audio_streams(&external_options.audio_path) {
+        Ok(streams) if streams.is_empty() => report.error(
+            "external_audio_options.audio_path",
+            "Audio file has no audio tracks".to_string(),
+        ),
+        Ok(_) => {}
+        Err(err) => report.error(
+            "external_audio_options.audio_path",
+            format!("Failed to probe audio file: {}", err),
+        ),
+    }
+
+    if external_options.trim_start_seconds < 0.0 {
+        report.error(
+            "external_audio_options.trim_start_seconds",
+            "Trim start cannot be negative".to_string(),
+        );
+    }
+
+    if let Some(trim_end) = external_options.trim_end_seconds {
+        if trim_end <= external_options.trim_start_seconds {
+            report.error(
+                "external_audio_options.trim_end_seconds",
+                "Trim end must be after the trim start".to_string(),
+            );
+        }
+    }
+
+    if !options.process_audio {
+        report.warning(
+            "external_audio_enabled",
+            "The external audio is ignored because audio is not exported".to_string(),
+        );
+    }
+
+    if external_options.mode == ExternalAudioMode::Add
+        && options.audio_track_options.mode == AudioTrackMode::Default
+    {
+        let has_audio = audio_utils::get_audio_streams(&options.input_video_path)
+            .is_ok_and(|streams| !streams.is_empty());
+        if !has_audio {
+            report.error(
+                "external_audio_options.mode",
+                "The video has no audio track to add the external audio to, use replace instead"
+                    .to_string(),
+            );
+        }
+    }
+}
+
+fn validate_subtitle_options(options: &VideoEditOptions, report: &mut ValidationReport) {
+    let subtitle_options = &options.subtitle_options;
+
+    if subtitle_options.uses_external_file() {
+        let subtitle_path = Path::new(&subtitle_options.subtitle_path);
+        if !subtitle_path.is_file() {
+            report.error(
+                "subtitle_options.subtitle_path",
+                format!(
+                    "Subtitle file does not exist: {}",
+                    subtitle_options.subtitle_path
+                ),
+            );
+        } else if !subtitle_path
+            .extension()
+            .map(|extension| extension.to_string_lossy().to_lowercase())
+            .is_some_and(|extension| ["srt", "ass", "ssa"].contains(&extension.as_str()))
+        {
+            report.error(
+                "subtitle_options.subtitle_path",
+                "Only SRT and ASS subtitle files are supported".to_string(),
+            );
+        }
+    }
+
+    let streams = match subtitle_utils::get_subtitle_streams(&options.input_video_path) {
+        Ok(streams) => streams,
+        Err(err) => {
+            report.error(
+                "subtitle_options",
+                format!("Failed to probe subtitle tracks: {}", err),
+            );
+            return;
+        }
+    };
+
+    match subtitle_options.mode {
+        SubtitleMode::SoftMux if options.silence_removal_enabled => report.error(
+            "subtitle_options.mode",
+            "Subtitles cannot be muxed when silence removal is enabled, burn them in instead"
+                .to_string(),
+        ),
+        SubtitleMode::Keep if options.silence_removal_enabled && !streams.is_empty() => report
+            .warning(
+                "subtitle_options.mode",
+                "Subtitle tracks are dropped because silence removal changes the timing"
+                    .to_string(),
+            ),
+        SubtitleMode::Keep | SubtitleMode::SoftMux
+            if streams.iter().any(|stream| !stream.text_based) =>
+        {
+            report.warning(
+                "subtitle_options.mode",
+                "Image based subtitle tracks cannot be stored in MP4 and are dropped".to_string(),
+            )
+        }
+        SubtitleMode::BurnIn if !subtitle_options.uses_external_file() => {
+            match streams
+                .iter()
+                .find(|stream| stream.subtitle_index == subtitle_options.subtitle_index)
+            {
+                None => report.error(
+                    "subtitle_options.subtitle_index",
+                    format!(
+                        "Subtitle track {} does not exist",
+                        subtitle_options.subtitle_index
+                    ),
+                ),
+                Some(stream) if !stream.text_based => report.error(
+                    "subtitle_options.subtitle_index",
+                    format!(
+                        "Subtitle track {} is image based ({}) and cannot be burned in",
+                        subtitle_options.subtitle_index, stream.codec
+                    ),
+                ),
+                Some(_) => {}
+            }
+        }
+        _ => {}
+    }
+}
+
+fn validate_frame_rate_options(
+    options: &VideoEditOptions,
+    video_info: &VideoInfo,
+    report: &mut ValidationReport,
+) {
+    let frame_rate_options = &options.frame_rate_options;
+
+    if !frame_rate_options.is_active() {
+        if video_info.variable_frame_rate {
+            report.warning(
+                "frame_rate_options.mode",
+                "The video has a variable frame rate, convert it to a constant one for editors"
+                    .to_string(),
+            );
+        }
+        return;
+    }
+
+    if let Some(frame_rate) = frame_rate_options.frame_rate {
+        if frame_rate_options.mode == FrameRateMode::Constant
+            && !(1.0..=240.0).contains(&frame_rate)
+        {
+            report.error(
+                "frame_rate_options.frame_rate",
+                "Frame rate must be between 1 and 240".to_string(),
+            );
+            return;
+        }
+    }
+
+    match frame_rate_utils::get_output_frame_rate(frame_rate_options, video_info.frame_rate) {
+        Some(frame_rate) if frame_rate <= 0.0 => report.error(
+            "frame_rate_options.mode",
+            "The frame rate of the video is unknown, pick a frame rate instead".to_string(),
+        ),
+        Some(frame_rate) if frame_rate > video_info.frame_rate + 0.01 => report.warning(
+            "frame_rate_options.frame_rate",
+            format!(
+                "{:.2} fps is above the source's {:.2} fps, frames will be duplicated",
+                frame_rate, video_info.frame_rate
+            ),
+        ),
+        _ => {}
+    }
+}
+
+fn validate_metadata_options(
+    options: &VideoEditOptions,
+    rotation: u32,
+    report: &mut ValidationReport,
+) {
+    let metadata_options = &options.metadata_options;
+
+    for (index, entry) in metadata_options.edits.iter().enumerate() {
+        let key = entry.key.trim();
+        if key.is_empty() || key.contains('=') {
+            report.error(
+                &format!("metadata_options.edits[{}].key", index),
+                format!("Invalid metadata field name: '{}'", entry.key),
+            );
+        } else if metadata_options.edits[..index]
+            .iter()
+            .any(|previous| previous.key.trim() == key)
+        {
+            report.warning(
+                &format!("metadata_options.edits[{}].key", index),
+                format!("Metadata field '{}' is set more than once", key),
+            );
+        }
+    }
+
+    if metadata_options.rotation_mode == RotationMode::Keep
+        && rotation != 0
+        && options.subtitle_options.mode == SubtitleMode::BurnIn
+    {
+        report.error(
+            "metadata_options.rotation_mode",
+            "Subtitles cannot be burned into a video whose rotation is only kept as metadata"
+                .to_string(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_report() -> ValidationReport {
+        ValidationReport {
+            valid: true,
+            issues: Vec::new(),
+        }
+    }
+
+    fn edit_options(overrides: serde_json::Value) -> VideoEditOptions {
+        let mut options = serde_json::json!({
+            "input_video_path": "input.mp4",
+            "output_video_path": "output",
+            "cut_options_enabled": false,
+            "cut_options": { "starting_time_string": "0", "end_time_string": "0" },
+            "crop_enabled": false,
+            "crop_options": { "starting_x_offset": 0, "starting_y_offset": 0, "width": 0, "height": 0 },
+            "compression_enabled": false,
+            "compression_options": {
+                "codec": "libx264",
+                "preset": "medium",
+                "crf": 23,
+                "bitrate": 2000,
+                "audio_codec": "copy",
+                "audio_bitrate": 0,
+            },
+            "resize_enabled": false,
+            "resize_options": { "width": 0, "height": 0 },
+            "process_audio": true,
+        });
+
+        for (key, value) in overrides.as_object().unwrap() {
+            match (options.get_mut(key), value) {
+                (Some(serde_json::Value::Object(existing)), serde_json::Value::Object(fields)) => {
+                    existing.extend(fields.clone())
+                }
+                _ => options[key] = value.clone(),
+            }
+        }
+
+        serde_json::from_value(options).unwrap()
+    }
+
+    fn severities(report: &ValidationReport) -> Vec<(String, ValidationSeverity)> {
+        report
+            .issues
+            .iter()
+            .map(|issue| (issue.field.clone(), issue.severity))
+            .collect()
+    }
+
+    #[test]
+    fn warns_about_odd_crop_sizes() {
+        let cases = [
+            ((0, 0, 640, 360), vec![]),
+            (
+                (0, 0, 641, 360),
+                vec![("crop_options.width", ValidationSeverity::Warning)],
+            ),
+            (
+                (0, 0, 640, 361),
+                vec![("crop_options.height", ValidationSeverity::Warning)],
+            ),
+            (
+                (0, 0, 1, 360),
+                vec![("crop_options.width", ValidationSeverity::Error)],
+            ),
+            (
+                (0, 0, 640, 0),
+                vec![("crop_options.height", ValidationSeverity::Error)],
+            ),
+            (
+                (2, 0, 1920, 360),
+                vec![("crop_options.width", ValidationSeverity::Error)],
+            ),
+            (
+                (0, -2, 640, 360),
+                vec![("crop_options.starting_y_offset", ValidationSeverity::Error)],
+            ),
+        ];
+
+        for ((x, y, width, height), expected) in cases {
+            let options = edit_options(serde_json::json!({
+                "crop_enabled": true,
+                "crop_options": {
+                    "starting_x_offset": x,
+                    "starting_y_offset": y,
+                    "width": width,
+                    "height": height,
+                },
+            }));
+            let mut report = empty_report();
+            validate_crop_options(&options, 1920, 1080, &mut report);
+
+            let expected: Vec<(String, ValidationSeverity)> = expected
+                .into_iter()
+                .map(|(field, severity)| (field.to_string(), severity))
+                .collect();
+            assert!(
+                severities(&report) == expected,
+                "crop {}x{} at {},{}",
+                width,
+                height,
+                x,
+                y
+            );
+            assert_eq!(
+                report.valid,
+                !expected
+                    .iter()
+                    .any(|(_, severity)| *severity == ValidationSeverity::Error)
+            );
+        }
+    }
+
+    #[test]
+    fn validates_rate_control() {
+        let cases = [
+            (
+                serde_json::json!({ "rate_control": "crf", "crf": 23 }),
+                None,
+            ),
+            (
+                serde_json::json!({ "rate_control": "crf", "crf": 52 }),
+                Some("compression_options.crf"),
+            ),
+            (
+                serde_json::json!({ "codec": "libsvtav1", "rate_control": "crf", "crf": 60 }),
+                None,
+            ),
+            (
+                serde_json::json!({ "codec": "libsvtav1", "rate_control": "two_pass_abr" }),
+                Some("compression_options.rate_control"),
+            ),
+            (
+                serde_json::json!({ "rate_control": "cbr", "bitrate": 0 }),
+                Some("compression_options.bitrate"),
+            ),
+            (
+                serde_json::json!({ "rate_control": "capped_crf", "max_bitrate": 0 }),
+                Some("compression_options.max_bitrate"),
+            ),
+            (
+                serde_json::json!({ "rate_control": "constrained_vbr", "max_bitrate": 1000 }),
+                Some("compression_options.max_bitrate"),
+            ),
+            (
+                serde_json::json!({ "rate_control": "constrained_vbr", "max_bitrate": 2000 }),
+                None,
+            ),
+            (
+                serde_json::json!({ "rate_control": "two_pass_abr", "buffer_size": 0 }),
+                Some("compression_options.buffer_size"),
+            ),
+        ];
+
+        for (compression_options, expected_error) in cases {
+            let options = edit_options(serde_json::json!({
+                "compression_enabled": true,
+                "compression_options": compression_options,
+            }));
+            let mut report = empty_report();
+            validate_compression_options(&options, &mut report);
+
+            let errors: Vec<String> = severities(&report)
+                .into_iter()
+                .filter(|(_, severity)| *severity == ValidationSeverity::Error)
+                .map(|(field, _)| field)
+                .collect();
+            let expected: Vec<String> = expected_error.into_iter().map(String::from).collect();
+            assert_eq!(errors, expected, "{}", compression_options);
+        }
+    }
+
+    #[test]
+    fn validates_extra_args() {
+        let cases: [(&[&str], &[usize]); 7] = [
+            (&["-g", "48"], &[]),
+            (&["-bf", "3", "-tag:v", "hvc1"], &[]),
+            (&["-c:v:0", "libx265"], &[0]),
+            (&["-b:v", "5M"], &[0]),
+            (&["-filter_complex_script", "graph.txt"], &[0]),
+            (&["-g", "48", "72"], &[2]),
+            (&["hvc1"], &[0]),
+        ];
+
+        for (extra_args, expected_errors) in cases {
+            let extra_args: Vec<String> = extra_args.iter().map(|arg| arg.to_string()).collect();
+            let mut report = empty_report();
+            validate_extra_args(&extra_args, &mut report);
+
+            let errors: Vec<String> = severities(&report)
+                .into_iter()
+                .filter(|(_, severity)| *severity == ValidationSeverity::Error)
+                .map(|(field, _)| field)
+                .collect();
+            let expected: Vec<String> = expected_errors
+                .iter()
+                .map(|index| format!("compression_options.advanced_options.extra_args[{}]", index))
+                .collect();
+            assert_eq!(errors, expected, "{:?}", extra_args);
+            assert_eq!(report.valid, expected_errors.is_empty());
+        }
+    }
+}
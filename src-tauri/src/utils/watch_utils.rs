@@ -0,0 +1,299 @@
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::handlers::video_handlers::check_if_file_is_video;
+use crate::utils::ffmpeg_utils;
+use crate::utils::preset_utils::{self, EditPreset};
+use crate::utils::validation_utils;
+
+const WATCH_POLL_INTERVAL_MS: u64 = 500;
+const DEFAULT_STABLE_SECONDS: u64 = 3;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct WatchFolderOptions {
+    pub watch_directory: String,
+    pub output_directory: String,
+    pub archive_directory: Option<String>,
+    pub stable_seconds: Option<u64>,
+    pub preset_name: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct WatchFolderStatus {
+    watching: bool,
+    watch_directory: String,
+    current_file: Option<String>,
+    // A stop lets the current file finish, the watcher ends once it is done.
+    stopping: bool,
+    pending_files: Vec<String>,
+    processed_files: Vec<String>,
+    failed_files: Vec<String>,
+    last_error: Option<String>,
+}
+
+struct PendingFile {
+    last_size: u64,
+    last_change: Instant,
+}
+
+lazy_static::lazy_static! {
+    static ref WATCH_FOLDER_STATUS: Mutex<WatchFolderStatus> = Mutex::new(WatchFolderStatus {
+        watching: false,
+        watch_directory: "".to_string(),
+        current_file: None,
+        stopping: false,
+        pending_files: Vec::new(),
+        processed_files: Vec::new(),
+        failed_files: Vec::new(),
+        last_error: None,
+    });
+
+    static ref WATCH_STOP_REQUESTED: Mutex<bool> = Mutex::new(false);
+}
+
+pub fn start_watch_folder(options: WatchFolderOptions, preset: EditPreset) -> Result<(), String> {
+    let watch_path = PathBuf::from(&options.watch_directory);
+    if !watch_path.is_dir() {
+        return Err(format!(
+            "Watch directory does not exist: {}",
+            options.watch_directory
+        ));
+    }
+
+    if !Path::new(&options.output_directory).is_dir() {
+        return Err(format!(
+            "Output directory does not exist: {}",
+            options.output_directory
+        ));
+    }
+
+    if Path::new(&options.output_directory) == watch_path {
+        return Err("Output directory must be different from the watch directory".to_string());
+    }
+
+    if let Some(archive_directory) = &options.archive_directory {
+        if Path::new(archive_directory) == watch_path {
+            return Err("Archive directory must be different from the watch directory".to_string());
+        }
+        fs::create_dir_all(archive_directory)
+            .map_err(|e| format!("Failed to create archive directory: {}", e))?;
+    }
+
+    let mut status = WATCH_FOLDER_STATUS.lock().unwrap();
+    if status.watching {
+        return Err(format!(
+            "Already watching directory: {}",
+            status.watch_directory
+        ));
+    }
+
+    let (tx, rx) = mpsc::channel::<notify::Result<Event>>();
+    let mut watcher = notify::recommended_watcher(tx)
+        .map_err(|e| format!("Failed to create file watcher: {}", e))?;
+    watcher
+        .watch(&watch_path, RecursiveMode::NonRecursive)
+        .map_err(|e| format!("Failed to watch directory: {}", e))?;
+
+    status.watching = true;
+    status.watch_directory = options.watch_directory.clone();
+    status.current_file = None;
+    status.stopping = false;
+    status.pending_files.clear();
+    status.processed_files.clear();
+    status.failed_files.clear();
+    status.last_error = None;
+    drop(status);
+
+    *WATCH_STOP_REQUESTED.lock().unwrap() = false;
+
+    println!(
+        "Watching directory: {} with preset: {}",
+        options.watch_directory, preset.name
+    );
+
+    thread::spawn(move || {
+        // The watcher stops emitting events once dropped, so it has to live on this thread.
+        let _watcher = watcher;
+        let stable_duration =
+            Duration::from_secs(options.stable_seconds.unwrap_or(DEFAULT_STABLE_SECONDS));
+        let mut pending: HashMap<PathBuf, PendingFile> = HashMap::new();
+
+        loop {
+            if *WATCH_STOP_REQUESTED.lock().unwrap() {
+                break;
+            }
+
+            match rx.recv_timeout(Duration::from_millis(WATCH_POLL_INTERVAL_MS)) {
+                Ok(Ok(event)) => {
+                    if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+                        for path in event.paths {
+                            if path.is_file() && check_if_file_is_video(&path.to_string_lossy()) {
+                                pending.entry(path).or_insert(PendingFile {
+                                    last_size: 0,
+                                    last_change: Instant::now(),
+                                });
+                            }
+                        }
+                    }
+                }
+                Ok(Err(e)) => {
+                    eprintln!("Watch error: {}", e);
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => {
+                    set_watch_error("File watcher disconnected".to_string());
+                    break;
+                }
+            }
+
+            let stable_files = collect_stable_files(&mut pending, stable_duration);
+            update_pending_files(&pending);
+
+            for path in &stable_files {
+                if *WATCH_STOP_REQUESTED.lock().unwrap() {
+                    break;
+                }
+
+                process_watched_file(path, &options, &preset);
+            }
+        }
+
+        let mut status = WATCH_FOLDER_STATUS.lock().unwrap();
+        status.watching = false;
+        status.current_file = None;
+        status.stopping = false;
+        status.pending_files.clear();
+        drop(status);
+
+        println!("Stopped watching directory: {}", options.watch_directory);
+    });
+
+    Ok(())
+}
+
+pub fn stop_watch_folder() {
+    *WATCH_STOP_REQUESTED.lock().unwrap() = true;
+
+    let mut status = WATCH_FOLDER_STATUS.lock().unwrap();
+    status.stopping = status.watching;
+}
+
+// Exports started from the app share the progress with watched files, so the watcher waits for
+// them to finish instead of running alongside.
+fn wait_for_video_job() -> Option<ffmpeg_utils::VideoJobGuard> {
+    loop {
+        if *WATCH_STOP_REQUESTED.lock().unwrap() {
+            return None;
+        }
+
+        if let Ok(job) = ffmpeg_utils::try_start_video_job() {
+            return Some(job);
+        }
+
+        thread::sleep(Duration::from_millis(WATCH_POLL_INTERVAL_MS));
+    }
+}
+
+pub fn get_watch_folder_status() -> WatchFolderStatus {
+    let status = WATCH_FOLDER_STATUS.lock().unwrap();
+    status.clone()
+}
+
+fn collect_stable_files(
+    pending: &mut HashMap<PathBuf, PendingFile>,
+    stable_duration: Duration,
+) -> Vec<PathBuf> {
+    let mut stable_files = Vec::new();
+
+    pending.retain(|path, pending_file| {
+        let size = match fs::metadata(path) {
+            Ok(metadata) => metadata.len(),
+            Err(_) => return false,
+        };
+
+        if size != pending_file.last_size {
+            pending_file.last_size = size;
+            pending_file.last_change = Instant::now();
+            return true;
+        }
+
+        if size > 0 && pending_file.last_change.elapsed() >= stable_duration {
+            stable_files.push(path.clone());
+            return false;
+        }
+
+        true
+    });
+
+    stable_files
+}
+
+fn process_watched_file(path: &Path, options: &WatchFolderOptions, preset: &EditPreset) {
+    let Some(_job) = wait_for_video_job() else {
+        return;
+    };
+
+    let path_str = path.to_string_lossy().to_string();
+    println!("Processing watched file: {}", path_str);
+
+    let mut status = WATCH_FOLDER_STATUS.lock().unwrap();
+    status.current_file = Some(path_str.clone());
+    drop(status);
+
+    let result = preset_utils::apply_preset(preset, &path_str, &options.output_directory)
+        .and_then(|edit_options| {
+            validation_utils::validate_edit_options(&edit_options).into_result()?;
+            ffmpeg_utils::process_video(edit_options)
+        })
+        .and_then(|_| match &options.archive_directory {
+            Some(archive_directory) => archive_file(path, Path::new(archive_directory)),
+            None => Ok(()),
+        });
+
+    let mut status = WATCH_FOLDER_STATUS.lock().unwrap();
+    status.current_file = None;
+    match result {
+        Ok(()) => status.processed_files.push(path_str),
+        Err(err) => {
+            eprintln!("Failed to process watched file {}: {}", path_str, err);
+            status.failed_files.push(path_str);
+            status.last_error = Some(err);
+        }
+    }
+    drop(status);
+}
+
+fn archive_file(path: &Path, archive_directory: &Path) -> Result<(), String> {
+    let file_name = path.file_name().ok_or("Failed to get file name")?;
+    let archive_path = ffmpeg_utils::get_unique_filename(&archive_directory.join(file_name));
+
+    // Renaming fails across volumes, so fall back to copying the file over.
+    if fs::rename(path, &archive_path).is_err() {
+        fs::copy(path, &archive_path).map_err(|e| format!("Failed to archive file: {}", e))?;
+        fs::remove_file(path).map_err(|e| format!("Failed to remove original file: {}", e))?;
+    }
+
+    Ok(())
+}
+
+fn update_pending_files(pending: &HashMap<PathBuf, PendingFile>) {
+    let mut status = WATCH_FOLDER_STATUS.lock().unwrap();
+    status.pending_files = pending
+        .keys()
+        .map(|path| path.to_string_lossy().to_string())
+        .collect();
+    drop(status);
+}
+
+fn set_watch_error(err: String) {
+    let mut status = WATCH_FOLDER_STATUS.lock().unwrap();
+    status.last_error = Some(err);
+    drop(status);
+}
@@ -1,13 +1,21 @@
 import { useEffect, useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import VideoView from "./components/VideoView";
-import { Button, Dropdown, type MenuProps, Modal, Progress, Space } from "antd";
+import { Button, Checkbox, Dropdown, InputNumber, type MenuProps, Modal, Progress, Select, Space } from "antd";
 import CutSegment from "./components/CutSegment";
 import CropSegment from "./components/CropSegment";
-import type { DependenciesSetUpInfo, SharedCutSegmentOptions, VideoCropPoints, VideoEditOptions, VideoInfo } from "./Logic/Interfaces/Interfaces";
+import type { CrfSearchResult, DependenciesSetUpInfo, EditPreset, QualityMetric, Settings, SharedCutSegmentOptions, VideoCropPoints, VideoEditOptions, VideoInfo } from "./Logic/Interfaces/Interfaces";
 import "./App.css";
 import CompressSegment from "./components/CompressSegment";
 import ResizeSegment from "./components/ResizeSegment";
+import AudioTracksSegment from "./components/AudioTracksSegment";
+import AudioLevelSegment from "./components/AudioLevelSegment";
+import AudioFormatSegment from "./components/AudioFormatSegment";
+import ExternalAudioSegment from "./components/ExternalAudioSegment";
+import SubtitleSegment from "./components/SubtitleSegment";
+import MetadataSegment from "./components/MetadataSegment";
+import FrameRateSegment from "./components/FrameRateSegment";
+import TransformSegment from "./components/TransformSegment";
 import { initiateVideoCropPoints, videoPathIsValid } from "./Logic/Utils/Utils";
 import { CropPointsContext, CutSegmentContext } from "./Logic/GlobalContexts";
 import VideoPathSelection from "./components/VideoPathSelection";
@@ -17,9 +25,14 @@ import type { UnlistenFn } from "@tauri-apps/api/event";
 import { check } from "@tauri-apps/plugin-updater";
 import { DownOutlined } from "@ant-design/icons";
 import { ExportTypes } from "./Logic/Enums/Enums";
-import { downloadDependencies, submitAudioOnly, submitVideo } from "./Logic/Utils/FfmpegUtils";
+import { downloadDependencies, extractFrame, extractSubtitles, previewSampleEncode, sanitizeVideo, searchCrf, submitAnimation, submitAudioOnly, submitVideo } from "./Logic/Utils/FfmpegUtils";
 import { updateApp } from "./Logic/Utils/UpdaterUtils";
 import { calculateAspectRatio } from "./Logic/Utils/AspectRatioUtils";
+import { mapCropPointsThroughTransform } from "./Logic/Utils/VideoCropUtils";
+
+const PRESET_KEY_PREFIX = "preset:";
+
+const DEFAULT_CRF_SEARCH_TARGETS: Record<QualityMetric, number> = { vmaf: 95, ssim: 0.98, psnr: 42 };
 
 function App() {
   const [ffmpegExists, setFfmpegExists] = useState(true);
@@ -39,6 +52,11 @@ function App() {
 
   const [currentOs, _] = useState(platform());
   const [downloadingDependencies, setDownloadingDependencies] = useState(false);
+  const [settings, setSettings] = useState<Settings | undefined>(undefined);
+  const [presets, setPresets] = useState<EditPreset[]>([]);
+  const [crfSearchMetric, setCrfSearchMetric] = useState<QualityMetric>("ssim");
+  const [crfSearchTarget, setCrfSearchTarget] = useState(DEFAULT_CRF_SEARCH_TARGETS.ssim);
+  const [crfSearchResult, setCrfSearchResult] = useState<CrfSearchResult | undefined>(undefined);
 
   const [videoEditOptions, setvideoEditOptions] = useState<VideoEditOptions>({
     input_video_path: "",
@@ -48,10 +66,34 @@ function App() {
     crop_enabled: false,
     crop_options: { starting_x_offset: 0, starting_y_offset: 0, width: 0, height: 0 },
     compression_enabled: false,
-    compression_options: { codec: "libx264", preset: "medium", using_crf: true, crf: 23, bitrate: 5550, audio_codec: "copy", audio_bitrate: 128, bitrate_type: 1 },
+    compression_options: {
+      codec: "libx264",
+      preset: "medium",
+      rate_control: "crf",
+      crf: 23,
+      bitrate: 5550,
+      max_bitrate: 8000,
+      buffer_size: null,
+      bitrate_unit: "kbps",
+      audio_codec: "copy",
+      audio_bitrate: 128,
+      advanced_options: { pixel_format: "auto", tune: null, profile: null, keyframe_interval: null, faststart: false, codec_params: [], extra_args: [] },
+    },
     resize_enabled: false,
-    resize_options: { width: 0, height: 0 },
+    resize_options: { width: 0, height: 0, mode: "fit", scaler: "bicubic", pad_color: "black" },
     process_audio: true,
+    silence_removal_enabled: false,
+    silence_removal_options: { noise_threshold_db: -30, min_silence_seconds: 1, padding_seconds: 0.25 },
+    audio_track_options: { mode: "default", selected_tracks: [] },
+    audio_level_options: { normalize_enabled: false, target_lufs: -16, true_peak_db: -1.5, loudness_range: 11, gain_db: 0, fade_in_seconds: 0, fade_out_seconds: 0 },
+    audio_format_options: { sample_rate: null, channel_layout: null, pcm_bit_depth: 16 },
+    external_audio_enabled: false,
+    external_audio_options: { audio_path: "", mode: "replace", offset_seconds: 0, trim_start_seconds: 0, trim_end_seconds: null, length_mode: "pad" },
+    subtitle_options: { mode: "keep", subtitle_path: "", subtitle_index: 0, language: null },
+    metadata_options: { mode: "preserve", edits: [], keep_chapters: true, rotation_mode: "transpose" },
+    frame_rate_options: { mode: "source", frame_rate: null },
+    transform_options: { rotation: "none", angle_degrees: 0, fill_color: "black", horizontal_flip: false, vertical_flip: false },
+    measure_quality: false,
   });
 
   const [cutSegmentSharedOptions, setCutSegmentSharedOptions] = useState<SharedCutSegmentOptions>({ startingSecond: 0, endingSecond: 0 });
@@ -102,7 +144,7 @@ function App() {
 
       setInteractingWithPaths(true);
 
-      setvideoEditOptions({ ...videoEditOptions, output_video_path: "" });
+      setvideoEditOptions({ ...videoEditOptions, output_video_path: settings?.default_output_directory ?? "" });
       video_selector_open = true;
       const path: string = await invoke("open_video");
       if (!videoPathIsValid(path)) {
@@ -129,8 +171,34 @@ function App() {
     }
   }
 
+  // Presets carry their own settings, including target sizes, so only the paths are taken from
+  // the editor.
+  async function exportWithPreset(name: string) {
+    let presetOptions: VideoEditOptions;
+    try {
+      presetOptions = await invoke<VideoEditOptions>("apply_preset", {
+        name,
+        inputVideoPath: videoEditOptions.input_video_path,
+        outputVideoPath: videoEditOptions.output_video_path,
+      });
+    } catch (e) {
+      alert(`Invalid options:\n${e}`);
+      return;
+    }
+
+    await submitVideo(presetOptions, setProcessingSubmission, setProcessingProgress);
+  }
+
   async function exportVideo(exportType: string) {
-    const localVideoEditOptions = videoEditOptions;
+    if (exportType.startsWith(PRESET_KEY_PREFIX)) {
+      await exportWithPreset(exportType.slice(PRESET_KEY_PREFIX.length));
+      return;
+    }
+
+    const localVideoEditOptions = {
+      ...videoEditOptions,
+      crop_options: mapCropPointsThroughTransform(videoEditOptions.crop_options, videoEditOptions.transform_options, videoInfo?.width ?? 0, videoInfo?.height ?? 0),
+    };
 
     switch (exportType) {
       case ExportTypes[1]:
@@ -144,6 +212,28 @@ function App() {
         localVideoEditOptions.process_audio = false;
         submitVideo(localVideoEditOptions, setProcessingSubmission, setProcessingProgress);
         break;
+      case ExportTypes[4]:
+        await extractFrame({
+          input_video_path: localVideoEditOptions.input_video_path,
+          output_directory: localVideoEditOptions.output_video_path,
+          time: localVideoEditOptions.cut_options_enabled ? localVideoEditOptions.cut_options.starting_time_string : "0",
+          format: "png",
+          crop_options: localVideoEditOptions.crop_enabled ? localVideoEditOptions.crop_options : null,
+          transform_options: localVideoEditOptions.transform_options,
+        });
+        break;
+      case ExportTypes[5]:
+      case ExportTypes[6]:
+        await submitAnimation(
+          localVideoEditOptions,
+          { format: exportType === ExportTypes[5] ? "gif" : "webp", fps: 15, max_width: 640, max_height: null, dither: "sierra", loop_count: 0, webp_quality: 75 },
+          setProcessingSubmission,
+          setProcessingProgress,
+        );
+        break;
+      case ExportTypes[7]:
+        await sanitizeVideo(localVideoEditOptions.input_video_path, localVideoEditOptions.output_video_path, setProcessingSubmission);
+        break;
     }
   }
 
@@ -155,6 +245,48 @@ function App() {
     setFfmpegExists(await invoke("check_ffmpeg_and_ffprobe"));
   }
 
+  async function loadPresets() {
+    try {
+      setPresets(await invoke<EditPreset[]>("list_presets"));
+    } catch (e) {
+      console.log("Failed to load presets", e);
+    }
+  }
+
+  async function loadCrfSearchMetric() {
+    if (await invoke<boolean>("is_libvmaf_available")) {
+      changeCrfSearchMetric("vmaf");
+    }
+  }
+
+  function changeCrfSearchMetric(metric: QualityMetric) {
+    setCrfSearchMetric(metric);
+    setCrfSearchTarget(DEFAULT_CRF_SEARCH_TARGETS[metric]);
+  }
+
+  async function findCrf() {
+    const result = await searchCrf(
+      videoEditOptions,
+      { metric: crfSearchMetric, target_score: crfSearchTarget, sample_count: 3, sample_seconds: 5, min_crf: null, max_crf: null },
+      setProcessingSubmission,
+    );
+    if (!result) {
+      return;
+    }
+
+    setvideoEditOptions((options) => ({ ...options, compression_options: { ...options.compression_options, rate_control: "crf", crf: result.crf } }));
+    setCrfSearchResult(result);
+  }
+
+  async function loadSettings() {
+    const loadedSettings = await invoke<Settings>("get_settings");
+    setSettings(loadedSettings);
+
+    if (loadedSettings.default_output_directory) {
+      setvideoEditOptions((options) => ({ ...options, output_video_path: loadedSettings.default_output_directory ?? "" }));
+    }
+  }
+
   async function checkForUpdates() {
     const update_check = await check();
     console.log("Checking for updates", update_check !== null);
@@ -164,6 +296,9 @@ function App() {
   useEffect(() => {
     checkForUpdates();
     checkFfmpegAndFfprobe();
+    loadSettings();
+    loadPresets();
+    loadCrfSearchMetric();
 
     let unlisten_drag_drop: UnlistenFn | undefined = undefined;
     let unlisten_drag_in: UnlistenFn | undefined = undefined;
@@ -229,6 +364,28 @@ function App() {
       key: ExportTypes[3],
       label: <div>Video Only</div>,
     },
+    {
+      key: ExportTypes[4],
+      label: <div>Frame (PNG)</div>,
+    },
+    {
+      key: ExportTypes[5],
+      label: <div>GIF</div>,
+    },
+    {
+      key: ExportTypes[6],
+      label: <div>Animated WebP</div>,
+    },
+    {
+      key: ExportTypes[7],
+      label: <div>Sanitized copy</div>,
+    },
+    {
+      key: "presets",
+      label: <div>Preset</div>,
+      disabled: presets.length === 0,
+      children: presets.map((preset) => ({ key: `${PRESET_KEY_PREFIX}${preset.name}`, label: <div>{preset.name}</div> })),
+    },
   ];
 
   return (
@@ -260,15 +417,89 @@ function App() {
                 </Button>
               </div>
               <CompressSegment
+                crfSearchResult={crfSearchResult}
                 onChange={(x, enabled) => setvideoEditOptions({ ...videoEditOptions, compression_enabled: enabled, compression_options: x })}
                 disabled={!videoPathIsValid(videoEditOptions.input_video_path)}
               />
+              <Checkbox
+                className={!videoPathIsValid(videoEditOptions.input_video_path) ? "disabled" : ""}
+                checked={videoEditOptions.measure_quality}
+                onChange={(e) => setvideoEditOptions((options) => ({ ...options, measure_quality: e.target.checked }))}
+              >
+                Measure quality after export
+              </Checkbox>
+              <Button
+                size="small"
+                className={!videoPathIsValid(videoEditOptions.input_video_path) || !videoEditOptions.compression_enabled ? "disabled" : ""}
+                onClick={() => previewSampleEncode(videoEditOptions, { sample_count: 3, sample_seconds: 5, measure_ssim: true }, setProcessingSubmission)}
+              >
+                Preview compression
+              </Button>
+              <Space.Compact size="small" className={!videoPathIsValid(videoEditOptions.input_video_path) || !videoEditOptions.compression_enabled ? "disabled" : ""}>
+                <Select
+                  value={crfSearchMetric}
+                  onChange={changeCrfSearchMetric}
+                  options={[
+                    { value: "vmaf", label: "VMAF" },
+                    { value: "ssim", label: "SSIM" },
+                    { value: "psnr", label: "PSNR" },
+                  ]}
+                  style={{ width: "80px" }}
+                />
+                <InputNumber
+                  min={0}
+                  max={crfSearchMetric === "ssim" ? 1 : 100}
+                  step={crfSearchMetric === "ssim" ? 0.005 : 0.5}
+                  value={crfSearchTarget}
+                  onChange={(e) => setCrfSearchTarget(e ?? DEFAULT_CRF_SEARCH_TARGETS[crfSearchMetric])}
+                  style={{ width: "80px" }}
+                />
+                <Button onClick={findCrf}>Find CRF</Button>
+              </Space.Compact>
+              <TransformSegment
+                disabled={!videoPathIsValid(videoEditOptions.input_video_path)}
+                onChange={(x) => setvideoEditOptions((options) => ({ ...options, transform_options: x }))}
+              />
               <ResizeSegment
                 onChange={(x, enabled) => setvideoEditOptions({ ...videoEditOptions, resize_enabled: enabled, resize_options: x })}
                 disabled={!videoPathIsValid(videoEditOptions.input_video_path)}
                 videoInfo={videoInfo}
                 videoNotCropped={videoEditOptions.crop_enabled === false}
               />
+              <FrameRateSegment
+                disabled={!videoPathIsValid(videoEditOptions.input_video_path)}
+                videoInfo={videoInfo}
+                onChange={(x) => setvideoEditOptions((options) => ({ ...options, frame_rate_options: x }))}
+              />
+              <AudioTracksSegment
+                videoPath={videoEditOptions.input_video_path}
+                disabled={!videoPathIsValid(videoEditOptions.input_video_path)}
+                onChange={(x) => setvideoEditOptions((options) => ({ ...options, audio_track_options: x }))}
+              />
+              <AudioLevelSegment
+                disabled={!videoPathIsValid(videoEditOptions.input_video_path)}
+                onChange={(x) => setvideoEditOptions((options) => ({ ...options, audio_level_options: x }))}
+              />
+              <AudioFormatSegment
+                disabled={!videoPathIsValid(videoEditOptions.input_video_path)}
+                onChange={(x) => setvideoEditOptions((options) => ({ ...options, audio_format_options: x }))}
+              />
+              <ExternalAudioSegment
+                disabled={!videoPathIsValid(videoEditOptions.input_video_path)}
+                onEnabledChange={(x) => setvideoEditOptions((options) => ({ ...options, external_audio_enabled: x }))}
+                onChange={(x) => setvideoEditOptions((options) => ({ ...options, external_audio_options: x }))}
+              />
+              <SubtitleSegment
+                videoPath={videoEditOptions.input_video_path}
+                disabled={!videoPathIsValid(videoEditOptions.input_video_path)}
+                onChange={(x) => setvideoEditOptions((options) => ({ ...options, subtitle_options: x }))}
+                onExtract={(subtitleIndex, format) => extractSubtitles(videoEditOptions, subtitleIndex, format)}
+              />
+              <MetadataSegment
+                disabled={!videoPathIsValid(videoEditOptions.input_video_path)}
+                rotation={videoInfo?.rotation ?? 0}
+                onChange={(x) => setvideoEditOptions((options) => ({ ...options, metadata_options: x }))}
+              />
             </div>
             <CropPointsContext.Provider
               value={{ cropPointPositions, setCropPointPositions, cropLinesUnlocked, setCropLinesUnlocked, cropEnabled: videoEditOptions.crop_enabled, resetCropPoints, setResetCropPoints }}
@@ -276,6 +507,7 @@ function App() {
               <VideoView
                 videoInfo={videoInfo}
                 videoPath={videoEditOptions.input_video_path}
+                transformOptions={videoEditOptions.transform_options}
                 onVideoPathClick={(): void => {
                   getVideoPath();
                 }}
@@ -285,6 +517,7 @@ function App() {
                   <VideoPathSelection videoEditOptions={videoEditOptions} videoPath={videoEditOptions.input_video_path} onClick={pickOutputPath} />
                   <CropSegment
                     videoInfo={videoInfo}
+                    videoPath={videoEditOptions.input_video_path}
                     onCropLinesLockStateChanged={(e) => setCropLinesUnlocked(e)}
                     onSegmentEnabledChanged={(e) => setvideoEditOptions({ ...videoEditOptions, crop_enabled: e })}
                     disabled={!videoPathIsValid(videoEditOptions.input_video_path)}
@@ -311,6 +544,7 @@ function App() {
           <div className={videoPathIsValid(videoEditOptions.input_video_path) ? "" : "disabled"}>
             <CutSegment
               onChange={(x, enabled) => setvideoEditOptions({ ...videoEditOptions, cut_options_enabled: enabled, cut_options: x })}
+              onSilenceRemovalChange={(enabled) => setvideoEditOptions((options) => ({ ...options, silence_removal_enabled: enabled }))}
               videoPath={videoEditOptions.input_video_path}
               videoDuration={videoInfo?.duration ?? "0:00:00.000"}
             />
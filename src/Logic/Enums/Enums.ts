@@ -13,9 +13,8 @@ export enum ExportTypes {
   VideoAndAudio = 1,
   AudioOnly = 2,
   VideoOnly = 3,
-}
-
-export enum BitrateType {
-  kbps = 1,
-  mbps = 2,
+  Frame = 4,
+  Gif = 5,
+  Webp = 6,
+  Sanitize = 7,
 }
@@ -8,12 +8,36 @@ export interface VideoCropPoints {
 export interface VideoCompressionOptions {
   codec: string;
   preset: string;
-  using_crf: boolean;
+  rate_control: RateControlMode;
   crf: number;
   bitrate: number;
-  bitrate_type: number;
+  max_bitrate: number;
+  buffer_size: number | null;
+  bitrate_unit: BitrateUnit;
   audio_codec: string;
   audio_bitrate: number;
+  advanced_options: AdvancedEncoderOptions;
+}
+
+export type RateControlMode = "crf" | "capped_crf" | "cbr" | "constrained_vbr" | "two_pass_abr";
+
+export type BitrateUnit = "kbps" | "mbps";
+
+export type PixelFormat = "auto" | "yuv420p" | "yuv420p10le" | "yuv422p" | "yuv422p10le" | "yuv444p" | "yuv444p10le";
+
+export interface EncoderParam {
+  key: string;
+  value: string;
+}
+
+export interface AdvancedEncoderOptions {
+  pixel_format: PixelFormat;
+  tune: string | null;
+  profile: string | null;
+  keyframe_interval: number | null;
+  faststart: boolean;
+  codec_params: EncoderParam[];
+  extra_args: string[];
 }
 
 export interface VideoCutOptions {
@@ -25,13 +49,29 @@ export interface VideoInfo {
   width: number;
   height: number;
   duration: string;
+  frame_rate: number;
+  variable_frame_rate: boolean;
+  rotation: number;
   aspect_ratio_width: number;
   aspect_ratio_height: number;
 }
 
+export type ResizeMode = "fit" | "fill" | "stretch" | "fit_width" | "fit_height";
+
+export type ScalingAlgorithm = "bicubic" | "bilinear" | "lanczos" | "spline" | "area" | "neighbor";
+
 export interface ResizeOptions {
   width: number;
   height: number;
+  mode: ResizeMode;
+  scaler: ScalingAlgorithm;
+  pad_color: string;
+}
+
+export interface SilenceRemovalOptions {
+  noise_threshold_db: number;
+  min_silence_seconds: number;
+  padding_seconds: number;
 }
 
 export interface VideoEditOptions {
@@ -46,6 +86,18 @@ export interface VideoEditOptions {
   resize_enabled: boolean;
   resize_options: ResizeOptions;
   process_audio: boolean;
+  silence_removal_enabled: boolean;
+  silence_removal_options: SilenceRemovalOptions;
+  audio_track_options: AudioTrackOptions;
+  audio_level_options: AudioLevelOptions;
+  audio_format_options: AudioFormatOptions;
+  external_audio_enabled: boolean;
+  external_audio_options: ExternalAudioOptions;
+  subtitle_options: SubtitleOptions;
+  metadata_options: MetadataOptions;
+  frame_rate_options: FrameRateOptions;
+  transform_options: TransformOptions;
+  measure_quality: boolean;
 }
 
 export interface VideoCropLineDisplacements {
@@ -59,6 +111,8 @@ export interface VideoEditProgress {
   progress: number;
   working: boolean;
   last_error: string | undefined;
+  loudness_measurement: LoudnessMeasurement | null;
+  quality_comparison: QualityComparisonResult | null;
 }
 
 export interface DependenciesSetUpInfo {
@@ -76,3 +130,315 @@ export interface SharedCutSegmentOptionsContext {
   sharedCutSegmentOptions: SharedCutSegmentOptions;
   setSharedCutSegmentOptions: (_: SharedCutSegmentOptions) => void;
 }
+
+export interface Settings {
+  version: number;
+  default_output_directory: string | null;
+  last_input_directory: string | null;
+  last_output_directory: string | null;
+  ffmpeg_path_override: string | null;
+  overwrite_policy: "rename" | "overwrite" | "skip";
+}
+
+export interface EditPreset {
+  name: string;
+  version: number;
+  built_in: boolean;
+}
+
+export interface ValidationIssue {
+  field: string;
+  severity: "error" | "warning";
+  message: string;
+}
+
+export interface ValidationReport {
+  valid: boolean;
+  issues: ValidationIssue[];
+}
+
+export interface CropDetectionResult {
+  crop_points: VideoCropPoints;
+  confidence: number;
+  samples_used: number;
+  samples_total: number;
+}
+
+export interface SceneChange {
+  time: string;
+  seconds: number;
+  score: number;
+  thumbnail_path: string | null;
+}
+
+export interface SceneDetectionResult {
+  threshold: number;
+  scenes: SceneChange[];
+}
+
+export interface SilenceInterval {
+  start: string;
+  end: string;
+  start_seconds: number;
+  end_seconds: number;
+  duration: number;
+}
+
+export interface SilenceDetectionResult {
+  noise_threshold_db: number;
+  min_silence_seconds: number;
+  total_silence_seconds: number;
+  silences: SilenceInterval[];
+}
+
+export type FrameImageFormat = "png" | "jpeg" | "webp";
+
+export interface FrameExtractionOptions {
+  input_video_path: string;
+  output_directory: string;
+  time: string;
+  format: FrameImageFormat;
+  crop_options: VideoCropPoints | null;
+  transform_options: TransformOptions;
+}
+
+export interface SpriteSheetFrame {
+  index: number;
+  time: string;
+  seconds: number;
+  x: number;
+  y: number;
+}
+
+export interface SpriteSheet {
+  image_path: string;
+  index_path: string;
+  columns: number;
+  rows: number;
+  thumbnail_width: number;
+  thumbnail_height: number;
+  interval_seconds: number;
+  frames: SpriteSheetFrame[];
+}
+
+export type AnimationFormat = "gif" | "webp" | "apng";
+
+export type GifDither = "none" | "bayer" | "floyd_steinberg" | "sierra";
+
+export interface AnimationExportOptions {
+  format: AnimationFormat;
+  fps: number;
+  max_width: number | null;
+  max_height: number | null;
+  dither: GifDither;
+  loop_count: number;
+  webp_quality: number;
+}
+
+export interface AudioStreamInfo {
+  stream_index: number;
+  audio_index: number;
+  codec: string;
+  channels: number;
+  channel_layout: string | null;
+  sample_rate: number | null;
+  bit_rate: number | null;
+  language: string | null;
+  title: string | null;
+  default: boolean;
+}
+
+export type AudioTrackMode = "default" | "keep" | "mix";
+
+export interface AudioTrackOptions {
+  mode: AudioTrackMode;
+  selected_tracks: number[];
+}
+
+export interface AudioLevelOptions {
+  normalize_enabled: boolean;
+  target_lufs: number;
+  true_peak_db: number;
+  loudness_range: number;
+  gain_db: number;
+  fade_in_seconds: number;
+  fade_out_seconds: number;
+}
+
+export interface LoudnessMeasurement {
+  input_i: number;
+  input_tp: number;
+  input_lra: number;
+  input_thresh: number;
+  target_offset: number;
+}
+
+export interface AudioFormatOptions {
+  sample_rate: number | null;
+  channel_layout: string | null;
+  pcm_bit_depth: number;
+}
+
+export type ExternalAudioMode = "replace" | "add";
+export type ExternalAudioLengthMode = "pad" | "shorten";
+
+export interface ExternalAudioOptions {
+  audio_path: string;
+  mode: ExternalAudioMode;
+  offset_seconds: number;
+  trim_start_seconds: number;
+  trim_end_seconds: number | null;
+  length_mode: ExternalAudioLengthMode;
+}
+
+export interface SubtitleStreamInfo {
+  stream_index: number;
+  subtitle_index: number;
+  codec: string;
+  text_based: boolean;
+  language: string | null;
+  title: string | null;
+  default: boolean;
+  forced: boolean;
+}
+
+export type SubtitleMode = "none" | "keep" | "soft_mux" | "burn_in";
+export type SubtitleFormat = "srt" | "ass";
+
+export interface SubtitleOptions {
+  mode: SubtitleMode;
+  subtitle_path: string;
+  subtitle_index: number;
+  language: string | null;
+}
+
+export type MetadataMode = "preserve" | "strip";
+export type RotationMode = "transpose" | "keep";
+
+export interface MetadataEntry {
+  key: string;
+  value: string;
+}
+
+export interface MetadataOptions {
+  mode: MetadataMode;
+  edits: MetadataEntry[];
+  keep_chapters: boolean;
+  rotation_mode: RotationMode;
+}
+
+export interface RemovedStreamTags {
+  stream_index: number;
+  codec_type: string;
+  tags: MetadataEntry[];
+}
+
+export interface RemovedStream {
+  stream_index: number;
+  codec_type: string;
+  codec: string | null;
+  reason: string;
+}
+
+export interface Chapter {
+  start_seconds: number;
+  end_seconds: number;
+  title: string | null;
+}
+
+export interface SanitizeReport {
+  output_path: string;
+  removed_format_tags: MetadataEntry[];
+  removed_stream_tags: RemovedStreamTags[];
+  removed_chapters: Chapter[];
+  removed_streams: RemovedStream[];
+}
+
+export interface QualityScores {
+  vmaf: number | null;
+  ssim: number | null;
+  psnr: number | null;
+}
+
+export interface QualitySegment {
+  start: string;
+  end: string;
+  start_seconds: number;
+  end_seconds: number;
+  scores: QualityScores;
+}
+
+export interface QualityComparisonResult {
+  scores: QualityScores;
+  segments: QualitySegment[];
+  vmaf_available: boolean;
+  warnings: string[];
+}
+
+export interface SampleEncodeOptions {
+  sample_count: number;
+  sample_seconds: number;
+  measure_ssim: boolean;
+}
+
+export interface SampleEncodeResult {
+  start: string;
+  start_seconds: number;
+  duration_seconds: number;
+  size_bytes: number;
+  encode_seconds: number;
+  ssim: number | null;
+}
+
+export interface SampleEncodePreview {
+  samples: SampleEncodeResult[];
+  output_length_seconds: number;
+  projected_size_bytes: number;
+  projected_size_mb: number;
+  encode_speed: number;
+  projected_encode_seconds: number;
+  ssim: number | null;
+}
+
+export type QualityMetric = "vmaf" | "ssim" | "psnr";
+
+export interface CrfSearchOptions {
+  metric: QualityMetric;
+  target_score: number;
+  sample_count: number;
+  sample_seconds: number;
+  min_crf: number | null;
+  max_crf: number | null;
+}
+
+export interface CrfSearchAttempt {
+  crf: number;
+  score: number;
+  projected_size_bytes: number;
+}
+
+export interface CrfSearchResult {
+  crf: number;
+  score: number;
+  target_met: boolean;
+  projected_size_bytes: number;
+  projected_size_mb: number;
+  attempts: CrfSearchAttempt[];
+}
+
+export type FrameRateMode = "source" | "constant" | "half";
+
+export interface FrameRateOptions {
+  mode: FrameRateMode;
+  frame_rate: number | null;
+}
+
+export type TransformRotation = "none" | "clockwise90" | "rotate180" | "clockwise270" | "angle";
+
+export interface TransformOptions {
+  rotation: TransformRotation;
+  angle_degrees: number;
+  fill_color: string;
+  horizontal_flip: boolean;
+  vertical_flip: boolean;
+}
@@ -1,10 +1,15 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { DependenciesSetUpInfo, VideoEditOptions, VideoEditProgress } from "../Interfaces/Interfaces";
+import type { AnimationExportOptions, CrfSearchOptions, CrfSearchResult, DependenciesSetUpInfo, FrameExtractionOptions, QualityComparisonResult, SampleEncodeOptions, SampleEncodePreview, SanitizeReport, SubtitleFormat, ValidationReport, VideoEditOptions, VideoEditProgress } from "../Interfaces/Interfaces";
 
 export async function submitVideo(videoEditOptions: VideoEditOptions, setProcessingSubmission: (processingSubmission: boolean) => void, setProcessingProgress: (processingProgress: number) => void) {
   const videoEditOptionsLocal = videoEditOptions;
 
-  await invoke("submit_video_for_editing", { options: videoEditOptionsLocal });
+  try {
+    await invoke("submit_video_for_editing", { options: videoEditOptionsLocal });
+  } catch (e) {
+    alert(`Invalid options:\n${e}`);
+    return;
+  }
 
   setProcessingSubmission(true);
   setProcessingProgress(0);
@@ -26,6 +31,65 @@ export async function submitVideo(videoEditOptions: VideoEditOptions, setProcess
     }
 
     if (newVideoInfo.progress === 100) {
+      if (newVideoInfo.loudness_measurement) {
+        console.log("Measured loudness", newVideoInfo.loudness_measurement);
+      }
+      if (newVideoInfo.quality_comparison) {
+        showQualityComparison(newVideoInfo.quality_comparison);
+      }
+      setProcessingProgress(100);
+      await new Promise((resolve) => setTimeout(resolve, 300));
+
+      setProcessingSubmission(false);
+      setProcessingProgress(0);
+    }
+
+    if (!newVideoInfo.working) {
+      setProcessingSubmission(false);
+      setProcessingProgress(0);
+      break;
+    }
+
+    await new Promise((resolve) => setTimeout(resolve, 50));
+  }
+}
+
+export async function submitAnimation(
+  videoEditOptions: VideoEditOptions,
+  animationOptions: AnimationExportOptions,
+  setProcessingSubmission: (processingSubmission: boolean) => void,
+  setProcessingProgress: (processingProgress: number) => void,
+) {
+  try {
+    await invoke("submit_animation_export", { options: videoEditOptions, animationOptions });
+  } catch (e) {
+    alert(`Invalid options:\n${e}`);
+    return;
+  }
+
+  setProcessingSubmission(true);
+  setProcessingProgress(0);
+
+  while (true) {
+    await new Promise((resolve) => setTimeout(resolve, 300));
+    const newVideoInfo = await invoke<VideoEditProgress>("get_video_progress_info");
+
+    if (newVideoInfo.last_error) {
+      setProcessingSubmission(false);
+      setProcessingProgress(0);
+
+      alert(`Something went wrong: ${newVideoInfo.last_error}`);
+      break;
+    }
+
+    if (newVideoInfo.working) {
+      setProcessingProgress(newVideoInfo.progress);
+    }
+
+    if (newVideoInfo.progress === 100) {
+      if (newVideoInfo.loudness_measurement) {
+        console.log("Measured loudness", newVideoInfo.loudness_measurement);
+      }
       setProcessingProgress(100);
       await new Promise((resolve) => setTimeout(resolve, 300));
 
@@ -48,7 +112,12 @@ export async function submitAudioOnly(
   setProcessingSubmission: (processingSubmission: boolean) => void,
   setProcessingProgress: (processingProgress: number) => void,
 ) {
-  await invoke("submit_audio_extraction", { options: videoEditOptions });
+  try {
+    await invoke("submit_audio_extraction", { options: videoEditOptions });
+  } catch (e) {
+    alert(`Invalid options:\n${e}`);
+    return;
+  }
 
   setProcessingSubmission(true);
   setProcessingProgress(0);
@@ -71,6 +140,9 @@ export async function submitAudioOnly(
     }
 
     if (newVideoInfo.progress === 100) {
+      if (newVideoInfo.loudness_measurement) {
+        console.log("Measured loudness", newVideoInfo.loudness_measurement);
+      }
       setProcessingProgress(100);
       await new Promise((resolve) => setTimeout(resolve, 300));
 
@@ -118,3 +190,98 @@ export async function downloadDependencies(
     setDownloadingDependencies(false);
   }
 }
+
+export async function validateEditOptions(videoEditOptions: VideoEditOptions): Promise<ValidationReport> {
+  return await invoke<ValidationReport>("validate_edit_options", { options: videoEditOptions });
+}
+
+export async function extractFrame(frameExtractionOptions: FrameExtractionOptions) {
+  try {
+    const outputPath = await invoke<string>("extract_frame", { options: frameExtractionOptions });
+    console.log("Frame saved to", outputPath);
+  } catch (e) {
+    alert(`Something went wrong: ${e}`);
+  }
+}
+
+export async function extractSubtitles(videoEditOptions: VideoEditOptions, subtitleIndex: number, format: SubtitleFormat) {
+  try {
+    const outputPath = await invoke<string>("extract_subtitles", { options: videoEditOptions, subtitleIndex, format });
+    console.log("Subtitles saved to", outputPath);
+  } catch (e) {
+    alert(`Something went wrong: ${e}`);
+  }
+}
+
+export async function sanitizeVideo(inputVideoPath: string, outputVideoPath: string, setProcessingSubmission: (processingSubmission: boolean) => void) {
+  setProcessingSubmission(true);
+  try {
+    const report = await invoke<SanitizeReport>("sanitize_video", { inputVideoPath, outputVideoPath });
+    const removed = [
+      ...report.removed_format_tags.map((tag) => `${tag.key}: ${tag.value}`),
+      ...report.removed_stream_tags.map((stream) => `Stream ${stream.stream_index} (${stream.codec_type}): ${stream.tags.map((tag) => tag.key).join(", ")}`),
+      ...report.removed_streams.map((stream) => `Stream ${stream.stream_index}: ${stream.reason}`),
+    ];
+    if (report.removed_chapters.length > 0) {
+      removed.push(`${report.removed_chapters.length} chapters`);
+    }
+
+    alert(removed.length > 0 ? `Removed:\n${removed.join("\n")}` : "Nothing to remove");
+  } catch (e) {
+    alert(`Something went wrong: ${e}`);
+  } finally {
+    setProcessingSubmission(false);
+  }
+}
+
+function showQualityComparison(comparison: QualityComparisonResult) {
+  const { vmaf, ssim, psnr } = comparison.scores;
+  const scores = [vmaf !== null ? `VMAF: ${vmaf.toFixed(2)}` : null, ssim !== null ? `SSIM: ${ssim.toFixed(4)}` : null, psnr !== null ? `PSNR: ${psnr.toFixed(2)} dB` : null].filter((score) => score);
+
+  console.log("Quality comparison", comparison);
+  alert([...scores, ...comparison.warnings].join("\n"));
+}
+
+export async function previewSampleEncode(videoEditOptions: VideoEditOptions, sampleOptions: SampleEncodeOptions, setProcessingSubmission: (processingSubmission: boolean) => void) {
+  setProcessingSubmission(true);
+  try {
+    const preview = await invoke<SampleEncodePreview>("preview_sample_encode", { options: videoEditOptions, sampleOptions });
+    const lines = [
+      `Projected size: ${preview.projected_size_mb.toFixed(1)} MB`,
+      `Encode speed: ${preview.encode_speed.toFixed(2)}x (about ${Math.ceil(preview.projected_encode_seconds / 60)} min)`,
+    ];
+    if (preview.ssim !== null) {
+      lines.push(`SSIM: ${preview.ssim.toFixed(4)}`);
+    }
+
+    alert(lines.join("\n"));
+  } catch (e) {
+    alert(`Something went wrong: ${e}`);
+  } finally {
+    setProcessingSubmission(false);
+  }
+}
+
+export async function searchCrf(videoEditOptions: VideoEditOptions, searchOptions: CrfSearchOptions, setProcessingSubmission: (processingSubmission: boolean) => void): Promise<CrfSearchResult | undefined> {
+  setProcessingSubmission(true);
+  try {
+    const result = await invoke<CrfSearchResult>("search_crf", { options: videoEditOptions, searchOptions });
+    const lines = [
+      `CRF: ${result.crf}`,
+      `${searchOptions.metric.toUpperCase()}: ${result.score.toFixed(searchOptions.metric === "ssim" ? 4 : 2)}`,
+      `Projected size: ${result.projected_size_mb.toFixed(1)} MB`,
+    ];
+    if (!result.target_met) {
+      lines.push(`No CRF in the searched range reached ${searchOptions.target_score}`);
+    }
+    lines.push("The CRF was applied to the compression settings");
+
+    console.log("CRF search", result);
+    alert(lines.join("\n"));
+    return result;
+  } catch (e) {
+    alert(`Something went wrong: ${e}`);
+  } finally {
+    setProcessingSubmission(false);
+  }
+}
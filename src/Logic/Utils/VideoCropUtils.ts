@@ -1,5 +1,5 @@
 import { HoveringOver } from "../Enums/Enums";
-import type { VideoCropLineDisplacements } from "../Interfaces/Interfaces";
+import type { TransformOptions, VideoCropLineDisplacements, VideoCropPoints } from "../Interfaces/Interfaces";
 
 export const determineIfHoveringOverLine = (e: React.MouseEvent<HTMLCanvasElement, MouseEvent>, canvasLineDisplacementRef: VideoCropLineDisplacements): HoveringOver | undefined => {
   const canvasRect = e.currentTarget.getBoundingClientRect();
@@ -157,3 +157,74 @@ export const updateCanvasLineDisplacement = (canvasLineDisplacementRef: VideoCro
     }
   }
 };
+
+// The preview only mirrors the video, it is never rotated, so crop lines are drawn on the source
+// frame as it is shown. The backend crops after the transform, so the picked area is undone from
+// the preview flips, turned with the frame and flipped again. A free angle keeps the bounding box
+// of the turned area.
+export const mapCropPointsThroughTransform = (cropPoints: VideoCropPoints, transformOptions: TransformOptions, width: number, height: number): VideoCropPoints => {
+  if (transformOptions.rotation === "none" || transformOptions.rotation === "rotate180") {
+    return cropPoints;
+  }
+
+  let left = cropPoints.starting_x_offset;
+  let top = cropPoints.starting_y_offset;
+  if (transformOptions.horizontal_flip) {
+    left = width - (left + cropPoints.width);
+  }
+  if (transformOptions.vertical_flip) {
+    top = height - (top + cropPoints.height);
+  }
+
+  let rotatedWidth = height;
+  let rotatedHeight = width;
+  let rotated: { left: number; top: number; width: number; height: number };
+
+  switch (transformOptions.rotation) {
+    case "clockwise90":
+      rotated = { left: height - (top + cropPoints.height), top: left, width: cropPoints.height, height: cropPoints.width };
+      break;
+    case "clockwise270":
+      rotated = { left: top, top: width - (left + cropPoints.width), width: cropPoints.height, height: cropPoints.width };
+      break;
+    default: {
+      const radians = (transformOptions.angle_degrees * Math.PI) / 180;
+      const sin = Math.sin(radians);
+      const cos = Math.cos(radians);
+      rotatedWidth = Math.trunc((width * Math.abs(cos) + height * Math.abs(sin)) / 2) * 2;
+      rotatedHeight = Math.trunc((width * Math.abs(sin) + height * Math.abs(cos)) / 2) * 2;
+
+      const corners = [
+        [left, top],
+        [left + cropPoints.width, top],
+        [left, top + cropPoints.height],
+        [left + cropPoints.width, top + cropPoints.height],
+      ].map(([x, y]) => [
+        rotatedWidth / 2 + (x - width / 2) * cos - (y - height / 2) * sin,
+        rotatedHeight / 2 + (x - width / 2) * sin + (y - height / 2) * cos,
+      ]);
+      const xs = corners.map(([x]) => Math.min(Math.max(x, 0), rotatedWidth));
+      const ys = corners.map(([, y]) => Math.min(Math.max(y, 0), rotatedHeight));
+
+      rotated = { left: Math.min(...xs), top: Math.min(...ys), width: Math.max(...xs) - Math.min(...xs), height: Math.max(...ys) - Math.min(...ys) };
+      break;
+    }
+  }
+
+  if (transformOptions.horizontal_flip) {
+    rotated.left = rotatedWidth - (rotated.left + rotated.width);
+  }
+  if (transformOptions.vertical_flip) {
+    rotated.top = rotatedHeight - (rotated.top + rotated.height);
+  }
+
+  const startingX = Math.floor(rotated.left / 2) * 2;
+  const startingY = Math.floor(rotated.top / 2) * 2;
+
+  return {
+    starting_x_offset: startingX,
+    starting_y_offset: startingY,
+    width: Math.min(Math.round(rotated.width / 2) * 2, rotatedWidth - startingX),
+    height: Math.min(Math.round(rotated.height / 2) * 2, rotatedHeight - startingY),
+  };
+};
@@ -0,0 +1,63 @@
+import { Select } from "antd";
+import { useEffect, useState } from "react";
+import type { AudioFormatOptions } from "../Logic/Interfaces/Interfaces";
+
+interface AudioFormatSegmentProps {
+  disabled: boolean;
+  onChange: (x: AudioFormatOptions) => void;
+}
+function AudioFormatSegment(props: AudioFormatSegmentProps) {
+  const [sampleRate, setSampleRate] = useState<number | null>(null);
+  const [channelLayout, setChannelLayout] = useState<string | null>(null);
+  const [pcmBitDepth, setPcmBitDepth] = useState(16);
+
+  useEffect(() => {
+    props.onChange({ sample_rate: sampleRate, channel_layout: channelLayout, pcm_bit_depth: pcmBitDepth });
+  }, [sampleRate, channelLayout, pcmBitDepth]);
+
+  return (
+    <div className={props.disabled ? "disabled" : ""}>
+      <div style={{ fontSize: "1.2em", fontWeight: "bold" }}>Audio format</div>
+      <div style={{ display: "flex", gap: "5px", marginTop: "5px" }}>
+        <Select
+          size="small"
+          value={sampleRate}
+          onChange={setSampleRate}
+          style={{ width: "110px" }}
+          options={[
+            { value: null, label: "Original rate" },
+            { value: 44100, label: "44.1 kHz" },
+            { value: 48000, label: "48 kHz" },
+            { value: 96000, label: "96 kHz" },
+          ]}
+        />
+        <Select
+          size="small"
+          value={channelLayout}
+          onChange={setChannelLayout}
+          style={{ width: "120px" }}
+          options={[
+            { value: null, label: "Original layout" },
+            { value: "mono", label: "Mono" },
+            { value: "stereo", label: "Stereo" },
+            { value: "5.1", label: "5.1" },
+          ]}
+        />
+        <Select
+          size="small"
+          value={pcmBitDepth}
+          onChange={setPcmBitDepth}
+          style={{ width: "90px" }}
+          title="WAV bit depth"
+          options={[
+            { value: 16, label: "16 bit" },
+            { value: 24, label: "24 bit" },
+            { value: 32, label: "32 bit" },
+          ]}
+        />
+      </div>
+    </div>
+  );
+}
+
+export default AudioFormatSegment;
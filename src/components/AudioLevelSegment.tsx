@@ -0,0 +1,50 @@
+import { Checkbox, InputNumber } from "antd";
+import { useEffect, useState } from "react";
+import type { AudioLevelOptions } from "../Logic/Interfaces/Interfaces";
+
+interface AudioLevelSegmentProps {
+  disabled: boolean;
+  onChange: (x: AudioLevelOptions) => void;
+}
+function AudioLevelSegment(props: AudioLevelSegmentProps) {
+  const [normalizeEnabled, setNormalizeEnabled] = useState(false);
+  const [targetLufs, setTargetLufs] = useState(-16);
+  const [truePeakDb, setTruePeakDb] = useState(-1.5);
+  const [gainDb, setGainDb] = useState(0);
+  const [fadeInSeconds, setFadeInSeconds] = useState(0);
+  const [fadeOutSeconds, setFadeOutSeconds] = useState(0);
+
+  useEffect(() => {
+    props.onChange({
+      normalize_enabled: normalizeEnabled,
+      target_lufs: targetLufs,
+      true_peak_db: truePeakDb,
+      loudness_range: 11,
+      gain_db: gainDb,
+      fade_in_seconds: fadeInSeconds,
+      fade_out_seconds: fadeOutSeconds,
+    });
+  }, [normalizeEnabled, targetLufs, truePeakDb, gainDb, fadeInSeconds, fadeOutSeconds]);
+
+  return (
+    <div className={props.disabled ? "disabled" : ""}>
+      <div style={{ display: "flex", gap: "10px" }}>
+        <div style={{ fontSize: "1.2em", fontWeight: "bold" }}>Loudness</div>
+        <Checkbox defaultChecked={false} onChange={(e) => setNormalizeEnabled(e.target.checked)} />
+      </div>
+      <div style={{ display: "flex", flexDirection: "column", gap: "5px", marginTop: "5px" }}>
+        <div className={normalizeEnabled ? "" : "disabled"} style={{ display: "flex", gap: "5px" }}>
+          <InputNumber size="small" addonAfter="LUFS" value={targetLufs} step={0.5} onChange={(value) => setTargetLufs(value ?? -16)} />
+          <InputNumber size="small" addonAfter="dBTP" value={truePeakDb} step={0.1} onChange={(value) => setTruePeakDb(value ?? -1.5)} />
+        </div>
+        <InputNumber size="small" addonBefore="Gain" addonAfter="dB" value={gainDb} step={0.5} onChange={(value) => setGainDb(value ?? 0)} />
+        <div style={{ display: "flex", gap: "5px" }}>
+          <InputNumber size="small" addonBefore="Fade in" addonAfter="s" min={0} value={fadeInSeconds} step={0.5} onChange={(value) => setFadeInSeconds(value ?? 0)} />
+          <InputNumber size="small" addonBefore="Fade out" addonAfter="s" min={0} value={fadeOutSeconds} step={0.5} onChange={(value) => setFadeOutSeconds(value ?? 0)} />
+        </div>
+      </div>
+    </div>
+  );
+}
+
+export default AudioLevelSegment;
@@ -0,0 +1,69 @@
+import { Checkbox, Radio } from "antd";
+import { invoke } from "@tauri-apps/api/core";
+import { useEffect, useState } from "react";
+import type { AudioStreamInfo, AudioTrackMode, AudioTrackOptions } from "../Logic/Interfaces/Interfaces";
+
+interface AudioTracksSegmentProps {
+  videoPath: string;
+  disabled: boolean;
+  onChange: (x: AudioTrackOptions) => void;
+}
+function AudioTracksSegment(props: AudioTracksSegmentProps) {
+  const [audioStreams, setAudioStreams] = useState<AudioStreamInfo[]>([]);
+  const [mode, setMode] = useState<AudioTrackMode>("default");
+  const [selectedTracks, setSelectedTracks] = useState<number[]>([]);
+
+  useEffect(() => {
+    props.onChange({ mode, selected_tracks: selectedTracks });
+  }, [mode, selectedTracks]);
+
+  useEffect(() => {
+    setMode("default");
+    setSelectedTracks([]);
+    setAudioStreams([]);
+
+    if (props.videoPath === "") {
+      return;
+    }
+
+    invoke<AudioStreamInfo[]>("get_audio_streams", { videoPath: props.videoPath })
+      .then((streams) => {
+        setAudioStreams(streams);
+        setSelectedTracks(streams.map((stream) => stream.audio_index));
+      })
+      .catch((e) => alert(`Something went wrong: ${e}`));
+  }, [props.videoPath]);
+
+  function trackLabel(stream: AudioStreamInfo) {
+    const details = [stream.title, stream.language, stream.codec, `${stream.channels}ch`].filter((detail) => detail);
+    return `Track ${stream.audio_index + 1} (${details.join(", ")})`;
+  }
+
+  if (audioStreams.length < 2) {
+    return null;
+  }
+
+  return (
+    <div className={props.disabled ? "disabled" : ""}>
+      <div style={{ fontSize: "1.2em", fontWeight: "bold" }}>Audio tracks</div>
+      <Radio.Group value={mode} onChange={(e) => setMode(e.target.value)} size="small" style={{ marginTop: "5px" }}>
+        <Radio.Button value="default">Default</Radio.Button>
+        <Radio.Button value="keep">Keep</Radio.Button>
+        <Radio.Button value="mix">Mix</Radio.Button>
+      </Radio.Group>
+      <div className={mode === "default" ? "disabled" : ""} style={{ display: "flex", flexDirection: "column", marginTop: "5px" }}>
+        <Checkbox.Group value={selectedTracks} onChange={(values) => setSelectedTracks(values as number[])}>
+          <div style={{ display: "flex", flexDirection: "column" }}>
+            {audioStreams.map((stream) => (
+              <Checkbox key={stream.audio_index} value={stream.audio_index}>
+                {trackLabel(stream)}
+              </Checkbox>
+            ))}
+          </div>
+        </Checkbox.Group>
+      </div>
+    </div>
+  );
+}
+
+export default AudioTracksSegment;
@@ -1,18 +1,20 @@
-import { Button, Checkbox, Dropdown, InputNumber, Radio, Space, type MenuProps } from "antd";
-import { useEffect, useRef, useState } from "react";
+import { Button, Checkbox, Dropdown, InputNumber, Select, Space, type MenuProps } from "antd";
+import { useEffect, useState } from "react";
 import { DownOutlined } from "@ant-design/icons";
-import type { VideoCompressionOptions } from "../Logic/Interfaces/Interfaces";
-import { BitrateType } from "../Logic/Enums/Enums";
+import type { AdvancedEncoderOptions, BitrateUnit, CrfSearchResult, RateControlMode, VideoCompressionOptions } from "../Logic/Interfaces/Interfaces";
+import EncoderOptionsSegment from "./EncoderOptionsSegment";
 
 interface CompressSegmentProps {
   disabled?: boolean;
+  // A new search result switches to CRF mode with the CRF that was found.
+  crfSearchResult?: CrfSearchResult;
   onChange: (x: VideoCompressionOptions, enabled: boolean) => void;
 }
 function CropSegment(props: CompressSegmentProps) {
   const [codecDropdownTitle, setCodecDropdownTitle] = useState("Codec: H.264");
   const [presetDropdownTitle, setPresetDropdownTitle] = useState("Medium (Default)");
   const [audioDropdownTitle, setAudioDropdownTitle] = useState("Audio: Copy");
-  const [bitrateType, setBitrateType] = useState(BitrateType.kbps);
+  const [bitrateUnit, setBitrateUnit] = useState<BitrateUnit>("kbps");
 
   const [segmentEnabled, setSegmentEnabled] = useState(false);
   const [selectedCodec, setSelectedCodec] = useState("libx264");
@@ -21,28 +23,38 @@ function CropSegment(props: CompressSegmentProps) {
 
   const [selectedCRF, setSelectedCRF] = useState(29);
   const [selectedBitrate, setSelectedBitrate] = useState(5550);
+  const [selectedMaxBitrate, setSelectedMaxBitrate] = useState(8000);
   const [selectedAudioBitrate, setSelectedAudioBitrate] = useState(128);
 
-  const [selectedQualityOption, setSelectedQualityOption] = useState(1);
-
-  const lastSelectedBitrateKbps = useRef(5500);
-  const lastSelectedBitrateMbps = useRef(5);
+  const [rateControl, setRateControl] = useState<RateControlMode>("crf");
+  const [showAdvancedOptions, setShowAdvancedOptions] = useState(false);
+  const [advancedOptions, setAdvancedOptions] = useState<AdvancedEncoderOptions | null>(null);
 
   useEffect(() => {
     props.onChange(
       {
         codec: selectedCodec,
         preset: selectedPreset,
+        rate_control: rateControl,
         crf: selectedCRF,
         bitrate: selectedBitrate,
-        using_crf: selectedQualityOption === 1,
+        max_bitrate: selectedMaxBitrate,
+        buffer_size: null,
+        bitrate_unit: bitrateUnit,
         audio_codec: selectedAudioCodec,
         audio_bitrate: selectedAudioBitrate,
-        bitrate_type: bitrateType,
+        advanced_options: advancedOptions ?? { pixel_format: "auto", tune: null, profile: null, keyframe_interval: null, faststart: false, codec_params: [], extra_args: [] },
       },
       segmentEnabled,
     );
-  }, [selectedCodec, selectedPreset, selectedCRF, selectedBitrate, selectedQualityOption, segmentEnabled, selectedAudioCodec, selectedAudioBitrate]);
+  }, [selectedCodec, selectedPreset, selectedCRF, selectedBitrate, selectedMaxBitrate, bitrateUnit, rateControl, segmentEnabled, selectedAudioCodec, selectedAudioBitrate, advancedOptions]);
+
+  useEffect(() => {
+    if (props.crfSearchResult) {
+      setSelectedCRF(props.crfSearchResult.crf);
+      setRateControl("crf");
+    }
+  }, [props.crfSearchResult]);
 
   const codecDropdownItems: { key: string; label: string }[] = [
     {
@@ -104,6 +116,18 @@ function CropSegment(props: CompressSegmentProps) {
       key: "libmp3lame",
       label: "MP3",
     },
+    {
+      key: "flac",
+      label: "FLAC",
+    },
+    {
+      key: "alac",
+      label: "ALAC",
+    },
+    {
+      key: "pcm",
+      label: "WAV (audio only)",
+    },
   ];
 
   const determinePreset = () => {
@@ -128,9 +152,40 @@ function CropSegment(props: CompressSegmentProps) {
     return "";
   };
 
+  // SVT-AV1 has no strict CBR and no two-pass mode in FFmpeg.
+  const rateControlOptions: { value: RateControlMode; label: string }[] = [
+    { value: "crf", label: "CRF" },
+    { value: "capped_crf", label: "Capped CRF" },
+    ...(selectedCodec === "libsvtav1" ? [] : [{ value: "cbr" as RateControlMode, label: "CBR" }]),
+    { value: "constrained_vbr", label: "Constrained VBR" },
+    ...(selectedCodec === "libsvtav1" ? [] : [{ value: "two_pass_abr" as RateControlMode, label: "Two-pass ABR" }]),
+  ];
+
+  const toggleBitrateUnit = () => {
+    const toMbps = (kbps: number) => Math.max(1, Math.round(kbps / 1000));
+    setSelectedBitrate(bitrateUnit === "kbps" ? toMbps(selectedBitrate) : selectedBitrate * 1000);
+    setSelectedMaxBitrate(bitrateUnit === "kbps" ? toMbps(selectedMaxBitrate) : selectedMaxBitrate * 1000);
+    setBitrateUnit(bitrateUnit === "kbps" ? "mbps" : "kbps");
+  };
+
+  const bitrateUnitAddon = (
+    <div
+      style={{ cursor: "pointer" }}
+      onMouseDown={(e) => {
+        e.preventDefault();
+        toggleBitrateUnit();
+      }}
+    >
+      {bitrateUnit}
+    </div>
+  );
+
   const handleCodecDropdownMenuClick: MenuProps["onClick"] = (e) => {
     setCodecDropdownTitle(`Codec: ${codecDropdownItems.find((x) => x.key === e.key)?.label}` || "Select a codec");
     setSelectedCodec(e.key);
+    if (e.key === "libsvtav1" && (rateControl === "cbr" || rateControl === "two_pass_abr")) {
+      setRateControl("constrained_vbr");
+    }
   };
 
   useEffect(() => {
@@ -206,16 +261,13 @@ function CropSegment(props: CompressSegmentProps) {
           </div>
 
           <div style={{ display: "flex", gap: "5px", flexDirection: "column" }}>
-            <Radio.Group onChange={(e) => setSelectedQualityOption(e.target.value)} value={selectedQualityOption}>
-              <Radio value={1}>CRF</Radio>
-              <Radio value={2}>Bitrate</Radio>
-            </Radio.Group>
+            <Select size="small" value={rateControl} onChange={setRateControl} style={{ width: "160px" }} options={rateControlOptions} />
 
-            {selectedQualityOption === 1 ? (
+            {(rateControl === "crf" || rateControl === "capped_crf") && (
               <InputNumber
                 style={{ maxWidth: "100px" }}
                 min={0}
-                max={51}
+                max={selectedCodec === "libsvtav1" ? 63 : 51}
                 addonAfter={"crf"}
                 type="number"
                 placeholder="CRF"
@@ -223,40 +275,42 @@ function CropSegment(props: CompressSegmentProps) {
                 value={selectedCRF}
                 onChange={(e) => setSelectedCRF(e ?? 0)}
               />
-            ) : (
-              <div style={{ display: "flex", gap: "10px", alignItems: "center" }}>
-                <InputNumber
-                  style={{ maxWidth: "200px" }}
-                  min={bitrateType === BitrateType.kbps ? 100 : 1}
-                  max={250000}
-                  addonAfter={
-                    <div
-                      style={{ cursor: "pointer" }}
-                      onMouseDown={(e) => {
-                        e.preventDefault();
-                        setBitrateType(bitrateType === BitrateType.kbps ? BitrateType.mbps : BitrateType.kbps);
-
-                        setSelectedBitrate(bitrateType === BitrateType.kbps ? lastSelectedBitrateMbps.current : lastSelectedBitrateKbps.current);
-                      }}
-                    >
-                      {BitrateType[bitrateType]}
-                    </div>
-                  }
-                  type="number"
-                  placeholder="Bitrate"
-                  defaultValue={5500}
-                  value={selectedBitrate}
-                  onChange={(e) => {
-                    setSelectedBitrate(e ?? 0);
-                    if (bitrateType === BitrateType.kbps) {
-                      lastSelectedBitrateKbps.current = e ?? 0;
-                    } else {
-                      lastSelectedBitrateMbps.current = e ?? 0;
-                    }
-                  }}
-                />
-              </div>
             )}
+            {rateControl !== "crf" && rateControl !== "capped_crf" && (
+              <InputNumber
+                style={{ maxWidth: "200px" }}
+                min={bitrateUnit === "kbps" ? 100 : 1}
+                max={250000}
+                addonBefore="Target"
+                addonAfter={bitrateUnitAddon}
+                type="number"
+                placeholder="Bitrate"
+                value={selectedBitrate}
+                onChange={(e) => setSelectedBitrate(e ?? 0)}
+              />
+            )}
+            {(rateControl === "capped_crf" || rateControl === "constrained_vbr") && (
+              <InputNumber
+                style={{ maxWidth: "200px" }}
+                min={bitrateUnit === "kbps" ? 100 : 1}
+                max={250000}
+                addonBefore="Max"
+                addonAfter={bitrateUnitAddon}
+                type="number"
+                placeholder="Max bitrate"
+                value={selectedMaxBitrate}
+                onChange={(e) => setSelectedMaxBitrate(e ?? 0)}
+              />
+            )}
+          </div>
+
+          <div style={{ display: "flex", gap: "5px", flexDirection: "column" }}>
+            <Checkbox checked={showAdvancedOptions} onChange={(e) => setShowAdvancedOptions(e.target.checked)}>
+              Advanced encoder options
+            </Checkbox>
+            <div style={{ display: showAdvancedOptions ? "block" : "none" }}>
+              <EncoderOptionsSegment codec={selectedCodec} onChange={setAdvancedOptions} />
+            </div>
           </div>
 
           <div style={{ display: "flex", gap: "10px", alignItems: "center" }}>
@@ -2,10 +2,12 @@ import { useContext, useEffect, useState } from "react";
 import { Button, Checkbox, InputNumber } from "antd";
 import { cropInputManuallyChangedInfo, CropPointsContext } from "../Logic/GlobalContexts";
 import { LockOutlined, UnlockOutlined } from "@ant-design/icons";
-import type { VideoCropPoints, VideoInfo } from "../Logic/Interfaces/Interfaces";
+import { invoke } from "@tauri-apps/api/core";
+import type { CropDetectionResult, VideoCropPoints, VideoInfo } from "../Logic/Interfaces/Interfaces";
 
 interface CropSegmentProps {
   videoInfo: VideoInfo | undefined;
+  videoPath: string;
   disabled: boolean;
   onSegmentEnabledChanged?: (enabled: boolean) => void;
   onCropLinesLockStateChanged: (enabled: boolean) => void;
@@ -16,6 +18,7 @@ interface CropSegmentProps {
 function CropSegment(props: CropSegmentProps) {
   const [segmentEnabled, setSegmentEnabled] = useState(false);
   const [cropLinesEnabled, setCropLinesEnabled] = useState(false);
+  const [detectingCrop, setDetectingCrop] = useState(false);
 
   const { cropPointPositions, setCropPointPositions } = useContext(CropPointsContext);
 
@@ -23,6 +26,25 @@ function CropSegment(props: CropSegmentProps) {
     props.onChange(cropPointPositions);
   }, [cropPointPositions]);
 
+  async function autoDetectCrop() {
+    try {
+      setDetectingCrop(true);
+      const result = await invoke<CropDetectionResult>("detect_crop", { videoPath: props.videoPath });
+
+      if (result.confidence === 0) {
+        alert("Could not detect any black bars.");
+        return;
+      }
+
+      setCropPointPositions(result.crop_points);
+      cropInputManuallyChangedInfo.manuallyChanged++;
+    } catch (e) {
+      alert(`Something went wrong: ${e}`);
+    } finally {
+      setDetectingCrop(false);
+    }
+  }
+
   return (
     <div className={props.disabled ? "disabled" : ""}>
       <div style={{ marginBottom: "10px", display: "flex", gap: "10px", placeSelf: "end" }}>
@@ -141,6 +163,9 @@ function CropSegment(props: CropSegmentProps) {
           >
             {cropLinesEnabled ? <UnlockOutlined /> : <LockOutlined />}
           </Button>
+          <Button loading={detectingCrop} onClick={autoDetectCrop}>
+            Auto
+          </Button>
           <Button onClick={props.onReset} type="primary">
             Reset
           </Button>
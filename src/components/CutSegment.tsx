@@ -1,12 +1,14 @@
-import { Checkbox, Input, Slider, type SliderSingleProps } from "antd";
+import { Button, Checkbox, Input, Slider, type SliderSingleProps } from "antd";
 import { useContext, useEffect, useState } from "react";
-import type { VideoCutOptions } from "../Logic/Interfaces/Interfaces";
+import { invoke } from "@tauri-apps/api/core";
+import type { SceneDetectionResult, VideoCutOptions } from "../Logic/Interfaces/Interfaces";
 import { CutSegmentContext } from "../Logic/GlobalContexts";
 
 interface CutSegmentProps {
   videoPath: string;
   videoDuration: string;
   onChange: (x: VideoCutOptions, enabled: boolean) => void;
+  onSilenceRemovalChange: (enabled: boolean) => void;
 }
 
 interface VideoDuration {
@@ -16,12 +18,20 @@ interface VideoDuration {
   microseconds: number;
 }
 
+const SCENE_SNAP_DISTANCE_SECONDS = 0.5;
+
 function CutSegment(props: CutSegmentProps) {
   const [totalSeconds, setTotalSeconds] = useState(0);
+  const [sceneSeconds, setSceneSeconds] = useState<number[]>([]);
+  const [detectingScenes, setDetectingScenes] = useState(false);
 
   const [startingSecond, setStartingSecond] = useState(0);
   const [endingSecond, setEndingSecond] = useState(0);
 
+  // Typed timestamps are passed on as written, frame based ones are resolved by the backend.
+  const [startingTimeInput, setStartingTimeInput] = useState<string | null>(null);
+  const [endingTimeInput, setEndingTimeInput] = useState<string | null>(null);
+
   const [startingInputError, setStartingInputError] = useState(false);
   const [endingInputError, setEndingInputError] = useState(false);
 
@@ -37,17 +47,20 @@ function CutSegment(props: CutSegmentProps) {
 
     setStartingSecond(0);
     setEndingSecond(totalSecs);
+    setStartingTimeInput(null);
+    setEndingTimeInput(null);
+    setSceneSeconds([]);
   }, [props.videoDuration]);
 
   useEffect(() => {
     props.onChange(
       {
-        starting_time_string: videoDurationToString(convertFromSeconds(startingSecond)),
-        end_time_string: videoDurationToString(convertFromSeconds(endingSecond)),
+        starting_time_string: startingTimeInput ?? videoDurationToString(convertFromSeconds(startingSecond)),
+        end_time_string: endingTimeInput ?? videoDurationToString(convertFromSeconds(endingSecond)),
       },
       segmentEnabled,
     );
-  }, [startingSecond, endingSecond, segmentEnabled]);
+  }, [startingSecond, endingSecond, startingTimeInput, endingTimeInput, segmentEnabled]);
 
   const parseVideoDuration = (duration: string) => {
     const [hours, minutes, secs] = duration.split(":");
@@ -67,6 +80,17 @@ function CutSegment(props: CutSegmentProps) {
     };
   };
 
+  // Accepts SS, MM:SS and HH:MM:SS with optional fractions, frame numbers and SMPTE timecodes
+  // need the frame rate and are left to the backend.
+  const parseTimestampSeconds = (timestamp: string): number | undefined => {
+    const parts = timestamp.split(":");
+    if (parts.length > 3 || parts.some((part) => !/^\d+(\.\d+)?$/.test(part.trim()))) {
+      return undefined;
+    }
+
+    return parts.reduce((total, part) => total * 60 + Number.parseFloat(part), 0);
+  };
+
   const videoDurationToString = (videoDuration: VideoDuration): string => {
     const { hours, minutes, seconds, microseconds } = videoDuration;
     const milliseconds = Math.round(microseconds / 1000);
@@ -96,27 +120,49 @@ function CutSegment(props: CutSegmentProps) {
     };
   };
 
+  const snapToScene = (second: number): number => {
+    const closestScene = sceneSeconds.find((sceneSecond) => Math.abs(sceneSecond - second) <= SCENE_SNAP_DISTANCE_SECONDS);
+    return closestScene ?? second;
+  };
+
+  async function detectScenes() {
+    try {
+      setDetectingScenes(true);
+      const result = await invoke<SceneDetectionResult>("detect_scenes", { videoPath: props.videoPath, threshold: null, generateThumbnails: false });
+      setSceneSeconds(result.scenes.map((scene) => scene.seconds));
+    } catch (e) {
+      alert(`Something went wrong: ${e}`);
+    } finally {
+      setDetectingScenes(false);
+    }
+  }
+
   const handleSliderInput = (e: number[]) => {
-    const newStartingSecond = e[0] < 0 ? 0 : e[0];
-    const newEndingSecond = e[1] > totalSeconds ? totalSeconds : e[1];
+    const newStartingSecond = e[0] < 0 ? 0 : snapToScene(e[0]);
+    const newEndingSecond = e[1] > totalSeconds ? totalSeconds : snapToScene(e[1]);
 
     setStartingSecond(newStartingSecond);
     setEndingSecond(newEndingSecond);
+    setStartingTimeInput(null);
+    setEndingTimeInput(null);
 
     setSharedCutSegmentOptions({ startingSecond: newStartingSecond, endingSecond: newEndingSecond });
   };
 
   const handleDurationInput = (durationString: string, starting: boolean) => {
-    if (!/^\d{2}:\d{2}:\d{2}/.test(durationString)) {
+    const timestamp = durationString.trim();
+    const setTimeInput = starting ? setStartingTimeInput : setEndingTimeInput;
+    setTimeInput(timestamp === "" ? null : timestamp);
+
+    const secs = parseTimestampSeconds(timestamp);
+    if (secs === undefined) {
       return;
     }
 
-    const duration = parseVideoDuration(durationString);
-    const secs = convertToSeconds(duration);
-
     if (starting) {
       if (secs < 0) {
         setStartingInputError(true);
+        setStartingTimeInput(null);
         setStartingSecond(0);
         setSharedCutSegmentOptions({ startingSecond: 0, endingSecond });
       } else {
@@ -127,6 +173,7 @@ function CutSegment(props: CutSegmentProps) {
     } else {
       if (secs > totalSeconds) {
         setEndingInputError(true);
+        setEndingTimeInput(null);
         setEndingSecond(totalSeconds);
         setSharedCutSegmentOptions({ startingSecond, endingSecond: totalSeconds });
       } else {
@@ -144,10 +191,24 @@ function CutSegment(props: CutSegmentProps) {
       <div style={{ marginBottom: "10px", display: "flex", gap: "10px" }}>
         <div style={{ fontSize: "1.2em", fontWeight: "bold" }}>Cut</div>
         <Checkbox defaultChecked={false} onChange={(e) => setSegmentEnabled(e.target.checked)} />
+        <Button disabled={!segmentEnabled} loading={detectingScenes} onClick={detectScenes} size="small">
+          Detect scenes
+        </Button>
+        <Checkbox defaultChecked={false} onChange={(e) => props.onSilenceRemovalChange(e.target.checked)}>
+          Remove silence
+        </Checkbox>
       </div>
 
       <div className={segmentEnabled ? "" : "disabled"}>
-        <Slider tooltip={{ formatter, placement: "left" }} range max={totalSeconds} value={[startingSecond, endingSecond]} step={0.05} onChange={handleSliderInput} />
+        <Slider
+          tooltip={{ formatter, placement: "left" }}
+          range
+          max={totalSeconds}
+          value={[startingSecond, endingSecond]}
+          step={0.05}
+          marks={Object.fromEntries(sceneSeconds.map((sceneSecond) => [sceneSecond, " "]))}
+          onChange={handleSliderInput}
+        />
         <div style={{ display: "flex", justifyContent: "space-between" }}>
           <div>
             <label htmlFor="startTimeInput">Start time:</label>
@@ -0,0 +1,96 @@
+import { Checkbox, Input, InputNumber, Select } from "antd";
+import { useEffect, useState } from "react";
+import type { AdvancedEncoderOptions, PixelFormat } from "../Logic/Interfaces/Interfaces";
+
+interface EncoderOptionsSegmentProps {
+  codec: string;
+  onChange: (x: AdvancedEncoderOptions) => void;
+}
+
+const tunes: Record<string, string[]> = {
+  libx264: ["film", "animation", "grain", "stillimage", "fastdecode", "zerolatency"],
+  libx265: ["animation", "grain", "fastdecode", "zerolatency"],
+};
+
+const profiles: Record<string, string[]> = {
+  libx264: ["baseline", "main", "high", "high10"],
+  libx265: ["main", "main10", "main12"],
+  libsvtav1: ["main", "high", "professional"],
+};
+
+function EncoderOptionsSegment(props: EncoderOptionsSegmentProps) {
+  const [pixelFormat, setPixelFormat] = useState<PixelFormat>("auto");
+  const [tune, setTune] = useState<string | null>(null);
+  const [profile, setProfile] = useState<string | null>(null);
+  const [keyframeInterval, setKeyframeInterval] = useState<number | null>(null);
+  const [faststart, setFaststart] = useState(false);
+  const [codecParams, setCodecParams] = useState("");
+  const [extraArgs, setExtraArgs] = useState("");
+
+  useEffect(() => {
+    setTune(null);
+    setProfile(null);
+  }, [props.codec]);
+
+  useEffect(() => {
+    props.onChange({
+      pixel_format: pixelFormat,
+      tune,
+      profile,
+      keyframe_interval: keyframeInterval,
+      faststart,
+      codec_params: codecParams
+        .split(":")
+        .filter((param) => param.trim() !== "")
+        .map((param) => {
+          const [key, ...value] = param.split("=");
+          return { key: key.trim(), value: value.join("=").trim() };
+        }),
+      extra_args: extraArgs.split(" ").filter((arg) => arg !== ""),
+    });
+  }, [pixelFormat, tune, profile, keyframeInterval, faststart, codecParams, extraArgs]);
+
+  return (
+    <div style={{ display: "flex", flexDirection: "column", gap: "5px" }}>
+      <div style={{ display: "flex", gap: "5px" }}>
+        <Select
+          size="small"
+          value={pixelFormat}
+          onChange={setPixelFormat}
+          style={{ width: "120px" }}
+          options={[
+            { value: "auto", label: "Source pixels" },
+            { value: "yuv420p", label: "yuv420p" },
+            { value: "yuv420p10le", label: "yuv420p 10 bit" },
+            { value: "yuv444p", label: "yuv444p" },
+          ]}
+        />
+        <Select
+          size="small"
+          value={tune}
+          onChange={setTune}
+          disabled={!tunes[props.codec]}
+          style={{ width: "110px" }}
+          options={[{ value: null, label: "No tune" }, ...(tunes[props.codec] ?? []).map((x) => ({ value: x, label: x }))]}
+        />
+        <Select
+          size="small"
+          value={profile}
+          onChange={setProfile}
+          style={{ width: "110px" }}
+          options={[{ value: null, label: "Auto profile" }, ...(profiles[props.codec] ?? []).map((x) => ({ value: x, label: x }))]}
+        />
+      </div>
+      <div style={{ display: "flex", gap: "10px", alignItems: "center" }}>
+        <InputNumber size="small" min={1} placeholder="Keyframe interval" addonAfter="frames" value={keyframeInterval} onChange={setKeyframeInterval} style={{ width: "180px" }} />
+        <Checkbox checked={faststart} onChange={(e) => setFaststart(e.target.checked)}>
+          Fast start
+        </Checkbox>
+      </div>
+      <Input size="small" placeholder="Encoder params (key=value:key=value)" value={codecParams} onChange={(e) => setCodecParams(e.target.value)} style={{ width: "300px" }} />
+      <Input size="small" placeholder="Extra FFmpeg arguments" value={extraArgs} onChange={(e) => setExtraArgs(e.target.value)} style={{ width: "300px" }} />
+    </div>
+  );
+}
+
+export default EncoderOptionsSegment;
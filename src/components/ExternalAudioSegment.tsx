@@ -0,0 +1,65 @@
+import { Button, Checkbox, InputNumber, Radio } from "antd";
+import { invoke } from "@tauri-apps/api/core";
+import { useEffect, useState } from "react";
+import type { ExternalAudioLengthMode, ExternalAudioMode, ExternalAudioOptions } from "../Logic/Interfaces/Interfaces";
+import { videoPathIsValid } from "../Logic/Utils/Utils";
+
+interface ExternalAudioSegmentProps {
+  disabled: boolean;
+  onEnabledChange: (x: boolean) => void;
+  onChange: (x: ExternalAudioOptions) => void;
+}
+function ExternalAudioSegment(props: ExternalAudioSegmentProps) {
+  const [enabled, setEnabled] = useState(false);
+  const [audioPath, setAudioPath] = useState("");
+  const [mode, setMode] = useState<ExternalAudioMode>("replace");
+  const [offsetSeconds, setOffsetSeconds] = useState(0);
+  const [lengthMode, setLengthMode] = useState<ExternalAudioLengthMode>("pad");
+
+  useEffect(() => {
+    props.onEnabledChange(enabled);
+  }, [enabled]);
+
+  useEffect(() => {
+    props.onChange({ audio_path: audioPath, mode, offset_seconds: offsetSeconds, trim_start_seconds: 0, trim_end_seconds: null, length_mode: lengthMode });
+  }, [audioPath, mode, offsetSeconds, lengthMode]);
+
+  async function pickAudioFile() {
+    const path: string = await invoke("pick_audio_file");
+    if (videoPathIsValid(path)) {
+      setAudioPath(path);
+    }
+  }
+
+  return (
+    <div className={props.disabled ? "disabled" : ""}>
+      <Checkbox checked={enabled} onChange={(e) => setEnabled(e.target.checked)}>
+        <span style={{ fontSize: "1.2em", fontWeight: "bold" }}>External audio</span>
+      </Checkbox>
+      <div className={enabled ? "" : "disabled"} style={{ display: "flex", flexDirection: "column", gap: "5px", marginTop: "5px" }}>
+        <div style={{ display: "flex", gap: "5px", alignItems: "center" }}>
+          <Button size="small" onClick={pickAudioFile}>
+            Select audio
+          </Button>
+          <span style={{ overflow: "hidden", textOverflow: "ellipsis", whiteSpace: "nowrap", maxWidth: "200px" }} title={audioPath}>
+            {audioPath === "" ? "No file selected" : audioPath}
+          </span>
+        </div>
+        <Radio.Group size="small" value={mode} onChange={(e) => setMode(e.target.value)}>
+          <Radio.Button value="replace">Replace</Radio.Button>
+          <Radio.Button value="add">Add track</Radio.Button>
+        </Radio.Group>
+        <div style={{ display: "flex", gap: "5px", alignItems: "center" }}>
+          <span>Offset</span>
+          <InputNumber size="small" value={offsetSeconds} step={0.1} addonAfter="s" onChange={(x) => setOffsetSeconds(x ?? 0)} style={{ width: "110px" }} />
+        </div>
+        <Radio.Group size="small" value={lengthMode} onChange={(e) => setLengthMode(e.target.value)}>
+          <Radio.Button value="pad">Pad with silence</Radio.Button>
+          <Radio.Button value="shorten">Shorten video</Radio.Button>
+        </Radio.Group>
+      </div>
+    </div>
+  );
+}
+
+export default ExternalAudioSegment;
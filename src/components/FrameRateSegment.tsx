@@ -0,0 +1,45 @@
+import { InputNumber, Radio } from "antd";
+import { useEffect, useState } from "react";
+import type { FrameRateMode, FrameRateOptions, VideoInfo } from "../Logic/Interfaces/Interfaces";
+
+interface FrameRateSegmentProps {
+  disabled: boolean;
+  videoInfo?: VideoInfo;
+  onChange: (x: FrameRateOptions) => void;
+}
+function FrameRateSegment(props: FrameRateSegmentProps) {
+  const [mode, setMode] = useState<FrameRateMode>("source");
+  const [frameRate, setFrameRate] = useState<number | null>(null);
+
+  useEffect(() => {
+    props.onChange({ mode, frame_rate: mode === "constant" ? frameRate : null });
+  }, [mode, frameRate]);
+
+  return (
+    <div className={props.disabled ? "disabled" : ""}>
+      <div style={{ fontSize: "1.2em", fontWeight: "bold" }}>Frame rate</div>
+      <div style={{ display: "flex", flexDirection: "column", gap: "5px", marginTop: "5px" }}>
+        <Radio.Group value={mode} onChange={(e) => setMode(e.target.value)} size="small">
+          <Radio.Button value="source">Source</Radio.Button>
+          <Radio.Button value="constant">Constant</Radio.Button>
+          <Radio.Button value="half">Half</Radio.Button>
+        </Radio.Group>
+        {mode === "constant" && (
+          <InputNumber
+            size="small"
+            min={1}
+            max={240}
+            addonAfter="fps"
+            placeholder={props.videoInfo ? props.videoInfo.frame_rate.toFixed(2) : "Source"}
+            value={frameRate}
+            onChange={setFrameRate}
+            style={{ width: "140px" }}
+          />
+        )}
+        {props.videoInfo?.variable_frame_rate && mode === "source" && <div style={{ color: "orange" }}>Variable frame rate, editors may go out of sync</div>}
+      </div>
+    </div>
+  );
+}
+
+export default FrameRateSegment;
@@ -0,0 +1,44 @@
+import { Checkbox, Input, Radio } from "antd";
+import { useEffect, useState } from "react";
+import type { MetadataMode, MetadataOptions, RotationMode } from "../Logic/Interfaces/Interfaces";
+
+interface MetadataSegmentProps {
+  disabled: boolean;
+  rotation: number;
+  onChange: (x: MetadataOptions) => void;
+}
+function MetadataSegment(props: MetadataSegmentProps) {
+  const [mode, setMode] = useState<MetadataMode>("preserve");
+  const [title, setTitle] = useState("");
+  const [keepChapters, setKeepChapters] = useState(true);
+  const [rotationMode, setRotationMode] = useState<RotationMode>("transpose");
+
+  useEffect(() => {
+    const edits = title === "" ? [] : [{ key: "title", value: title }];
+    props.onChange({ mode, edits, keep_chapters: keepChapters, rotation_mode: rotationMode });
+  }, [mode, title, keepChapters, rotationMode]);
+
+  return (
+    <div className={props.disabled ? "disabled" : ""}>
+      <div style={{ fontSize: "1.2em", fontWeight: "bold" }}>Metadata</div>
+      <div style={{ display: "flex", flexDirection: "column", gap: "5px", marginTop: "5px" }}>
+        <Radio.Group value={mode} onChange={(e) => setMode(e.target.value)} size="small">
+          <Radio.Button value="preserve">Preserve</Radio.Button>
+          <Radio.Button value="strip">Strip</Radio.Button>
+        </Radio.Group>
+        <Input size="small" placeholder="Title" value={title} onChange={(e) => setTitle(e.target.value)} style={{ width: "200px" }} />
+        <Checkbox checked={keepChapters} onChange={(e) => setKeepChapters(e.target.checked)}>
+          Keep chapters
+        </Checkbox>
+        {props.rotation !== 0 && (
+          <Radio.Group value={rotationMode} onChange={(e) => setRotationMode(e.target.value)} size="small">
+            <Radio.Button value="transpose">Rotate frames</Radio.Button>
+            <Radio.Button value="keep">Keep rotation tag</Radio.Button>
+          </Radio.Group>
+        )}
+      </div>
+    </div>
+  );
+}
+
+export default MetadataSegment;
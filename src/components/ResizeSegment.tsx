@@ -1,5 +1,5 @@
-import { Checkbox, InputNumber } from "antd";
-import type { VideoInfo } from "../Logic/Interfaces/Interfaces";
+import { Checkbox, Input, InputNumber, Select } from "antd";
+import type { ResizeMode, ResizeOptions, ScalingAlgorithm, VideoInfo } from "../Logic/Interfaces/Interfaces";
 import { useEffect, useState } from "react";
 import { LockOutlined, UnlockOutlined } from "@ant-design/icons";
 
@@ -7,7 +7,7 @@ interface ResizeSegmentProps {
   videoInfo: VideoInfo | undefined;
   disabled: boolean;
   videoNotCropped: boolean;
-  onChange: (x: { width: number; height: number }, enabled: boolean) => void;
+  onChange: (x: ResizeOptions, enabled: boolean) => void;
 }
 function ResizeSegment(props: ResizeSegmentProps) {
   const [segmentEnabled, setSegmentEnabled] = useState(false);
@@ -17,10 +17,13 @@ function ResizeSegment(props: ResizeSegmentProps) {
   });
 
   const [resizeRatioLocked, setResizeRatioLocked] = useState(true);
+  const [mode, setMode] = useState<ResizeMode>("fit");
+  const [scaler, setScaler] = useState<ScalingAlgorithm>("bicubic");
+  const [padColor, setPadColor] = useState("black");
 
   useEffect(() => {
-    props.onChange(videoDimensions, segmentEnabled && props.videoNotCropped);
-  }, [videoDimensions, segmentEnabled, props.videoNotCropped]);
+    props.onChange({ ...videoDimensions, mode, scaler, pad_color: padColor }, segmentEnabled && props.videoNotCropped);
+  }, [videoDimensions, mode, scaler, padColor, segmentEnabled, props.videoNotCropped]);
 
   useEffect(() => {
     setVideoDimensions({
@@ -60,7 +63,38 @@ function ResizeSegment(props: ResizeSegmentProps) {
 
       {!props.videoNotCropped && segmentEnabled && <div style={{ color: "red", fontSize: "0.8em" }}>Cannot resize if crop is enabled.</div>}
 
-      <div className={segmentEnabled ? "" : "disabled"} style={{ display: "flex", gap: "10px", alignItems: "center", height: "100%", marginTop: "25px" }}>
+      <div className={segmentEnabled ? "" : "disabled"} style={{ display: "flex", gap: "5px", marginTop: "10px" }}>
+        <Select
+          size="small"
+          value={mode}
+          onChange={setMode}
+          style={{ width: "110px" }}
+          options={[
+            { value: "fit", label: "Fit" },
+            { value: "fill", label: "Fill" },
+            { value: "stretch", label: "Stretch" },
+            { value: "fit_width", label: "Width only" },
+            { value: "fit_height", label: "Height only" },
+          ]}
+        />
+        <Select
+          size="small"
+          value={scaler}
+          onChange={setScaler}
+          style={{ width: "100px" }}
+          options={[
+            { value: "bicubic", label: "Bicubic" },
+            { value: "bilinear", label: "Bilinear" },
+            { value: "lanczos", label: "Lanczos" },
+            { value: "spline", label: "Spline" },
+            { value: "area", label: "Area" },
+            { value: "neighbor", label: "Nearest" },
+          ]}
+        />
+        {mode === "fit" && <Input size="small" placeholder="Pad color" value={padColor} onChange={(e) => setPadColor(e.target.value)} style={{ width: "90px" }} />}
+      </div>
+
+      <div className={segmentEnabled ? "" : "disabled"} style={{ display: "flex", gap: "10px", alignItems: "center", height: "100%", marginTop: "15px" }}>
         <div style={{ display: "flex", gap: "5px", flexDirection: "column" }}>
           <div
             style={{
@@ -73,6 +107,7 @@ function ResizeSegment(props: ResizeSegmentProps) {
           >
             <div>Width:</div>
             <InputNumber
+              disabled={mode === "fit_height"}
               value={videoDimensions.width}
               placeholder="1920"
               onChange={(value) => {
@@ -91,6 +126,7 @@ function ResizeSegment(props: ResizeSegmentProps) {
           >
             <div>Height:</div>
             <InputNumber
+              disabled={mode === "fit_width"}
               value={videoDimensions.height}
               title="height"
               placeholder="1080"
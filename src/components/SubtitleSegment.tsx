@@ -0,0 +1,105 @@
+import { Button, Radio, Select } from "antd";
+import { invoke } from "@tauri-apps/api/core";
+import { useEffect, useState } from "react";
+import type { SubtitleFormat, SubtitleMode, SubtitleOptions, SubtitleStreamInfo } from "../Logic/Interfaces/Interfaces";
+import { videoPathIsValid } from "../Logic/Utils/Utils";
+
+interface SubtitleSegmentProps {
+  videoPath: string;
+  disabled: boolean;
+  onChange: (x: SubtitleOptions) => void;
+  onExtract: (subtitleIndex: number, format: SubtitleFormat) => void;
+}
+function SubtitleSegment(props: SubtitleSegmentProps) {
+  const [subtitleStreams, setSubtitleStreams] = useState<SubtitleStreamInfo[]>([]);
+  const [mode, setMode] = useState<SubtitleMode>("keep");
+  const [subtitlePath, setSubtitlePath] = useState("");
+  const [subtitleIndex, setSubtitleIndex] = useState(0);
+  const [extractFormat, setExtractFormat] = useState<SubtitleFormat>("srt");
+
+  useEffect(() => {
+    props.onChange({ mode, subtitle_path: subtitlePath, subtitle_index: subtitleIndex, language: null });
+  }, [mode, subtitlePath, subtitleIndex]);
+
+  useEffect(() => {
+    setSubtitleStreams([]);
+    setSubtitleIndex(0);
+
+    if (props.videoPath === "") {
+      return;
+    }
+
+    invoke<SubtitleStreamInfo[]>("get_subtitle_streams", { videoPath: props.videoPath })
+      .then(setSubtitleStreams)
+      .catch((e) => alert(`Something went wrong: ${e}`));
+  }, [props.videoPath]);
+
+  async function pickSubtitleFile() {
+    const path: string = await invoke("pick_subtitle_file");
+    if (videoPathIsValid(path)) {
+      setSubtitlePath(path);
+    }
+  }
+
+  function trackLabel(stream: SubtitleStreamInfo) {
+    const details = [stream.title, stream.language, stream.codec].filter((detail) => detail);
+    return `Track ${stream.subtitle_index + 1} (${details.join(", ")})`;
+  }
+
+  const usesExternalFile = mode === "soft_mux" || (mode === "burn_in" && subtitlePath !== "");
+  const textStreams = subtitleStreams.filter((stream) => stream.text_based);
+
+  return (
+    <div className={props.disabled ? "disabled" : ""}>
+      <div style={{ fontSize: "1.2em", fontWeight: "bold" }}>Subtitles</div>
+      <Radio.Group value={mode} onChange={(e) => setMode(e.target.value)} size="small" style={{ marginTop: "5px" }}>
+        <Radio.Button value="none">None</Radio.Button>
+        <Radio.Button value="keep">Keep</Radio.Button>
+        <Radio.Button value="soft_mux">Add file</Radio.Button>
+        <Radio.Button value="burn_in">Burn in</Radio.Button>
+      </Radio.Group>
+      <div style={{ display: "flex", flexDirection: "column", gap: "5px", marginTop: "5px" }}>
+        <div className={mode === "soft_mux" || mode === "burn_in" ? "" : "disabled"} style={{ display: "flex", gap: "5px", alignItems: "center" }}>
+          <Button size="small" onClick={pickSubtitleFile}>
+            Select file
+          </Button>
+          <span style={{ overflow: "hidden", textOverflow: "ellipsis", whiteSpace: "nowrap", maxWidth: "200px" }} title={subtitlePath}>
+            {subtitlePath === "" ? "No file selected" : subtitlePath}
+          </span>
+          {subtitlePath !== "" && (
+            <Button size="small" onClick={() => setSubtitlePath("")}>
+              Clear
+            </Button>
+          )}
+        </div>
+        {textStreams.length > 0 && (
+          <div style={{ display: "flex", gap: "5px" }}>
+            <Select
+              size="small"
+              value={subtitleIndex}
+              onChange={setSubtitleIndex}
+              style={{ width: "200px" }}
+              options={textStreams.map((stream) => ({ value: stream.subtitle_index, label: trackLabel(stream) }))}
+              disabled={mode === "burn_in" && usesExternalFile}
+            />
+            <Select
+              size="small"
+              value={extractFormat}
+              onChange={setExtractFormat}
+              style={{ width: "70px" }}
+              options={[
+                { value: "srt", label: "SRT" },
+                { value: "ass", label: "ASS" },
+              ]}
+            />
+            <Button size="small" onClick={() => props.onExtract(subtitleIndex, extractFormat)}>
+              Extract
+            </Button>
+          </div>
+        )}
+      </div>
+    </div>
+  );
+}
+
+export default SubtitleSegment;
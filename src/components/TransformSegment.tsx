@@ -0,0 +1,50 @@
+import { Checkbox, Input, InputNumber, Radio } from "antd";
+import { useEffect, useState } from "react";
+import type { TransformOptions, TransformRotation } from "../Logic/Interfaces/Interfaces";
+
+interface TransformSegmentProps {
+  disabled: boolean;
+  onChange: (x: TransformOptions) => void;
+}
+function TransformSegment(props: TransformSegmentProps) {
+  const [rotation, setRotation] = useState<TransformRotation>("none");
+  const [angleDegrees, setAngleDegrees] = useState(0);
+  const [fillColor, setFillColor] = useState("black");
+  const [horizontalFlip, setHorizontalFlip] = useState(false);
+  const [verticalFlip, setVerticalFlip] = useState(false);
+
+  useEffect(() => {
+    props.onChange({ rotation, angle_degrees: angleDegrees, fill_color: fillColor, horizontal_flip: horizontalFlip, vertical_flip: verticalFlip });
+  }, [rotation, angleDegrees, fillColor, horizontalFlip, verticalFlip]);
+
+  return (
+    <div className={props.disabled ? "disabled" : ""}>
+      <div style={{ fontSize: "1.2em", fontWeight: "bold" }}>Rotate and flip</div>
+      <div style={{ display: "flex", flexDirection: "column", gap: "5px", marginTop: "5px" }}>
+        <Radio.Group value={rotation} onChange={(e) => setRotation(e.target.value)} size="small">
+          <Radio.Button value="none">0°</Radio.Button>
+          <Radio.Button value="clockwise90">90°</Radio.Button>
+          <Radio.Button value="rotate180">180°</Radio.Button>
+          <Radio.Button value="clockwise270">270°</Radio.Button>
+          <Radio.Button value="angle">Angle</Radio.Button>
+        </Radio.Group>
+        {rotation === "angle" && (
+          <div style={{ display: "flex", gap: "5px" }}>
+            <InputNumber size="small" min={-360} max={360} addonAfter="°" value={angleDegrees} onChange={(e) => setAngleDegrees(e ?? 0)} style={{ width: "110px" }} />
+            <Input size="small" placeholder="Fill color" value={fillColor} onChange={(e) => setFillColor(e.target.value)} style={{ width: "90px" }} />
+          </div>
+        )}
+        <div>
+          <Checkbox checked={horizontalFlip} onChange={(e) => setHorizontalFlip(e.target.checked)}>
+            Mirror
+          </Checkbox>
+          <Checkbox checked={verticalFlip} onChange={(e) => setVerticalFlip(e.target.checked)}>
+            Flip vertically
+          </Checkbox>
+        </div>
+      </div>
+    </div>
+  );
+}
+
+export default TransformSegment;
@@ -2,13 +2,14 @@ import { convertFileSrc } from "@tauri-apps/api/core";
 import { useContext, useEffect, useRef, useState } from "react";
 import { getCanvasToVideoSizeDifference, videoPathIsValid } from "../Logic/Utils/Utils";
 import { canvasLineDisplacementRef, clickedLineInfo, cropInputManuallyChangedInfo, CropPointsContext, CutSegmentContext } from "../Logic/GlobalContexts";
-import type { VideoCropPoints, VideoInfo } from "../Logic/Interfaces/Interfaces";
+import type { TransformOptions, VideoCropPoints, VideoInfo } from "../Logic/Interfaces/Interfaces";
 import { HoveringOver } from "../Logic/Enums/Enums";
 import { determineIfHoveringOverLine, updateCanvasLineDisplacement } from "../Logic/Utils/VideoCropUtils";
 
 interface VideoViewProps {
   videoInfo: VideoInfo | undefined;
   videoPath: string;
+  transformOptions?: TransformOptions;
   onVideoPathClick: () => void;
 }
 
@@ -181,11 +182,21 @@ function VideoView(props: VideoViewProps) {
     redrawCanvas();
   }, [props.videoPath]);
 
+  // Mirroring keeps the frame size, so the crop lines stay on the flipped preview. A 180° turn is
+  // the same as flipping both ways.
+  const getPreviewTransform = () => {
+    const rotated180 = props.transformOptions?.rotation === "rotate180";
+    const horizontal = (props.transformOptions?.horizontal_flip ?? false) !== rotated180;
+    const vertical = (props.transformOptions?.vertical_flip ?? false) !== rotated180;
+
+    return `scale(${horizontal ? -1 : 1}, ${vertical ? -1 : 1})`;
+  };
+
   function RenderVideoElement() {
     if (videoPathIsValid(props.videoPath)) {
       return (
         <div className="video-container">
-          <video ref={videoRef} key={props.videoPath} controls={!cropLinesUnlocked}>
+          <video ref={videoRef} key={props.videoPath} controls={!cropLinesUnlocked} style={{ transform: getPreviewTransform() }}>
             <source src={convertFileSrc(props.videoPath)} />
           </video>
           <canvas